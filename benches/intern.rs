@@ -0,0 +1,79 @@
+#![feature(test)]
+
+extern crate test;
+
+use libintern::{FastInterner, Interner, InternerBuilder};
+use test::Bencher;
+
+/// The number of distinct items every benchmark interns.
+const DISTINCT: usize = 1000;
+
+fn words() -> Vec<String> {
+    // Every word is interned twice, so that both the hits and the misses are measured
+    (0..2 * DISTINCT).map(|i| format!("word{}", i % DISTINCT)).collect()
+}
+
+#[bench]
+fn interner(b: &mut Bencher) {
+    let words = words();
+    b.iter(|| {
+        let interner = Interner::new();
+        for word in &words {
+            test::black_box(interner.intern(word.clone()));
+        }
+        interner.len()
+    });
+}
+
+#[bench]
+fn interner_boxed(b: &mut Bencher) {
+    let words = words();
+    b.iter(|| {
+        let interner = Interner::new_boxed();
+        for word in &words {
+            test::black_box(interner.intern(word.clone()));
+        }
+        interner.len()
+    });
+}
+
+#[bench]
+fn fast_interner(b: &mut Bencher) {
+    let words = words();
+    b.iter(|| {
+        let interner = FastInterner::new();
+        for word in &words {
+            test::black_box(interner.intern(word.clone()));
+        }
+        interner.len()
+    });
+}
+
+/// A handful of keywords, each looked up many times.
+fn keywords() -> Vec<&'static str> {
+    ["fn", "let", "if", "else", "match", "while", "for", "return"].repeat(100)
+}
+
+#[bench]
+fn keywords_linear(b: &mut Bencher) {
+    let keywords = keywords();
+    b.iter(|| {
+        let interner = InternerBuilder::new().build();
+        for &keyword in &keywords {
+            test::black_box(interner.intern(keyword));
+        }
+        interner.len()
+    });
+}
+
+#[bench]
+fn keywords_hashed(b: &mut Bencher) {
+    let keywords = keywords();
+    b.iter(|| {
+        let interner = InternerBuilder::new().linear_threshold(0).build();
+        for &keyword in &keywords {
+            test::black_box(interner.intern(keyword));
+        }
+        interner.len()
+    });
+}
@@ -0,0 +1,122 @@
+use alloc::sync::Arc;
+use core::{borrow::Borrow, hash::{BuildHasher, Hash}};
+
+use hashbrown::{Equivalent, HashSet};
+
+use crate::DefaultHashBuilder;
+
+/// An interner which hands out reference-counted items.
+///
+/// Unlike the references produced by an [`Interner`](crate::Interner),
+/// the items are not bound to the lifetime of the interner, so they can be stored
+/// in long-lived structures and even outlive the interner.
+/// Equal items are still deduplicated, use [`Arc::ptr_eq`] to compare them cheaply:
+///
+/// ```
+/// use std::sync::Arc;
+/// use libintern::ArcInterner;
+///
+/// let mut interner = ArcInterner::new();
+/// let a = interner.intern(String::from("a"));
+/// let other_a = interner.intern(String::from("a"));
+/// drop(interner);
+/// assert!(Arc::ptr_eq(&a, &other_a));
+/// ```
+///
+/// This trades the zero-overhead references for the lifetime freedom,
+/// every item is a separate allocation with a reference count.
+pub struct ArcInterner<T, S = DefaultHashBuilder> {
+    items: HashSet<Arc<T>, S>
+}
+
+impl<T: Eq + Hash> ArcInterner<T> {
+    pub fn new() -> Self {
+        Self::with_hasher(DefaultHashBuilder::default())
+    }
+}
+
+impl<T: Eq + Hash, S> ArcInterner<T, S> {
+    /// Create an interner which uses the given hasher to hash the items.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self { items: HashSet::with_hasher(hash_builder) }
+    }
+
+    /// The number of distinct items held by this interner.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if no items are held by this interner.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T: Eq + Hash, S: BuildHasher> ArcInterner<T, S> {
+    /// Intern an item.
+    ///
+    /// This item is dropped if an item equal to this one is already interned,
+    /// in which case the already interned item is returned instead.
+    pub fn intern(&mut self, item: T) -> Arc<T> {
+        if let Some(interned) = self.items.get(&item) {
+            return Arc::clone(interned)
+        }
+        let interned = Arc::new(item);
+        self.items.insert(Arc::clone(&interned));
+        interned
+    }
+
+    /// Get an already interned item, without interning it.
+    ///
+    /// The item can be given by any borrowed form of it.
+    pub fn get<Q>(&self, item: &Q) -> Option<Arc<T>>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash
+    {
+        self.items.get(&Lookup(item)).map(Arc::clone)
+    }
+}
+
+/// A borrowed form of an item, used to look up the held items.
+///
+/// `Arc<T>` only borrows as `T`, not as every borrowed form of `T`.
+#[derive(Hash)]
+struct Lookup<'q, Q: ?Sized>(&'q Q);
+
+impl<'q, Q: ?Sized + Eq, T: Borrow<Q>> Equivalent<Arc<T>> for Lookup<'q, Q> {
+    fn equivalent(&self, key: &Arc<T>) -> bool {
+        self.0 == (**key).borrow()
+    }
+}
+
+impl<T: Eq + Hash, S: Default> Default for ArcInterner<T, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::ArcInterner;
+
+    #[test]
+    fn arc_interner_test() {
+        let mut int = ArcInterner::new();
+        let a = int.intern(String::from("a"));
+        let b = int.intern(String::from("b"));
+        let other_a = int.intern(String::from("a"));
+        assert!(Arc::ptr_eq(&a, &other_a));
+        assert!(!Arc::ptr_eq(&a, &b));
+        assert_eq!(int.len(), 2);
+        assert!(Arc::ptr_eq(&int.get("b").unwrap(), &b));
+        assert!(int.get("c").is_none());
+
+        // The items outlive the interner
+        drop(int);
+        assert_eq!(*a, "a");
+        assert_eq!(Arc::strong_count(&a), 2);
+    }
+}
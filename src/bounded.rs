@@ -0,0 +1,121 @@
+use core::{borrow::Borrow, fmt, hash::{BuildHasher, Hash}};
+
+use crate::{DefaultHashBuilder, Intern, Interner};
+
+/// An interner which holds at most a fixed number of items.
+///
+/// The memory for all the items is allocated upfront, in a single holder,
+/// so the interner never grows. Once the budget is exhausted,
+/// interning a new item fails, but interning an already interned item still succeeds:
+///
+/// ```
+/// use libintern::BoundedInterner;
+///
+/// let interner = BoundedInterner::new(1);
+/// let a = interner.intern('a').unwrap();
+/// assert!(interner.intern('b').is_err());
+/// assert_eq!(interner.intern('a').unwrap(), a);
+/// ```
+pub struct BoundedInterner<'a, T: 'a + Eq, S = DefaultHashBuilder> {
+    items: Interner<'a, T, S>,
+    max_items: usize
+}
+
+/// The error returned when an item is interned into a full [`BoundedInterner`].
+///
+/// Contains the item which couldn't be interned.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InternFull<T>(pub T);
+
+impl<T> fmt::Display for InternFull<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the interner is full")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> std::error::Error for InternFull<T> {}
+
+impl<'a, T: 'a + Eq> BoundedInterner<'a, T> {
+    /// Create an interner which holds at most `max_items` items.
+    pub fn new(max_items: usize) -> Self {
+        Self::with_hasher(max_items, DefaultHashBuilder::default())
+    }
+}
+
+impl<'a, T: 'a + Eq, S> BoundedInterner<'a, T, S> {
+    /// Create an interner which holds at most `max_items` items
+    /// and uses the given hasher to hash the items.
+    pub fn with_hasher(max_items: usize, hash_builder: S) -> Self {
+        Self { items: Interner::with_capacity_and_hasher(max_items, hash_builder), max_items }
+    }
+
+    /// The maximum number of items this interner can hold.
+    pub fn max_items(&self) -> usize {
+        self.max_items
+    }
+
+    /// The number of distinct items held by this interner.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if no items are held by this interner.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns true if no more new items can be interned.
+    pub fn is_full(&self) -> bool {
+        self.len() >= self.max_items
+    }
+}
+
+impl<'a, T: 'a + Eq + Hash, S: BuildHasher> BoundedInterner<'a, T, S> {
+    /// Intern an item.
+    ///
+    /// Works like [`Interner::intern`], but if the item isn't interned yet
+    /// and the interner is full, the item is given back in an [`InternFull`] error.
+    pub fn intern(&self, item: T) -> Result<Intern<'_, T>, InternFull<T>> {
+        let hash = self.items.hash_builder.hash_one(&item);
+        let mut state = self.items.state.borrow_mut();
+        let symbol = match state.find_interned(hash, &item) {
+            Some(symbol) => symbol,
+            None if state.symbols.len() >= self.max_items => return Err(InternFull(item)),
+            None => state.hold_new_symbol(hash, item)
+        };
+        drop(state);
+        Ok(self.items.resolve(symbol))
+    }
+
+    /// Get a reference to an already interned item, without interning it.
+    ///
+    /// The item can be given by any borrowed form of it.
+    pub fn get_interned<Q>(&self, item: &Q) -> Option<Intern<'_, T>>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash
+    {
+        self.items.get_interned(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BoundedInterner, InternFull};
+
+    #[test]
+    fn bounded_interner_test() {
+        let int = BoundedInterner::new(2);
+        let a = int.intern(String::from("a")).unwrap();
+        int.intern(String::from("b")).unwrap();
+        assert!(int.is_full());
+        assert_eq!(int.intern(String::from("c")), Err(InternFull(String::from("c"))));
+        assert_eq!(int.intern(String::from("a")), Ok(a));
+        assert_eq!(int.len(), 2);
+        assert_eq!(int.get_interned("a"), Some(a));
+        assert_eq!(int.get_interned("c"), None);
+        // The memory was allocated upfront
+        assert_eq!(int.items.stats().holders, 1);
+    }
+}
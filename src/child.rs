@@ -0,0 +1,129 @@
+use alloc::rc::Rc;
+use core::{borrow::Borrow, hash::{BuildHasher, Hash}};
+
+use crate::{DefaultHashBuilder, Intern, Interner};
+
+/// An interner which shares the items of a parent interner.
+///
+/// Interning an item looks for an equal item in the parent interner,
+/// and if there is one, a reference to the item held by the parent is returned.
+/// Only the items which aren't held by the parent are held by the child itself.
+/// An item the child already holds keeps being returned by the child,
+/// even if an equal item is interned by the parent later, so equal items
+/// interned by the child always get the same reference.
+///
+/// This is useful when many short-lived interners share a long-lived vocabulary:
+///
+/// ```
+/// use std::rc::Rc;
+/// use libintern::{ChildInterner, Interner};
+///
+/// let vocabulary = Rc::new(Interner::new());
+/// let keyword = vocabulary.intern("fn");
+///
+/// let child = ChildInterner::with_parent(Rc::clone(&vocabulary));
+/// assert_eq!(child.intern("fn"), keyword);
+/// child.intern("main");
+/// assert_eq!(child.local().len(), 1);
+/// assert_eq!(vocabulary.len(), 1);
+/// ```
+///
+/// The parent is shared through an `Rc`, as an [`Interner`] can't be shared between threads anyway.
+/// Unlike [`Interner`], a child interner can't be sent to another thread either,
+/// because the parent interner may still be used on the current one.
+pub struct ChildInterner<'a, T: 'a + Eq, S = DefaultHashBuilder> {
+    parent: Rc<Interner<'a, T, S>>,
+    local: Interner<'a, T, S>
+}
+
+impl<'a, T: 'a + Eq, S: Clone> ChildInterner<'a, T, S> {
+    /// Create an empty child interner of the given parent interner.
+    ///
+    /// The child uses a copy of the parent's hasher to hash the items.
+    pub fn with_parent(parent: Rc<Interner<'a, T, S>>) -> Self {
+        let local = Interner::with_hasher(parent.hash_builder.clone());
+        Self { parent, local }
+    }
+}
+
+impl<'a, T: 'a + Eq, S> ChildInterner<'a, T, S> {
+    /// The parent interner.
+    pub fn parent(&self) -> &Rc<Interner<'a, T, S>> {
+        &self.parent
+    }
+
+    /// The interner which holds the items not held by the parent.
+    pub fn local(&self) -> &Interner<'a, T, S> {
+        &self.local
+    }
+}
+
+impl<'a, T: 'a + Eq + Hash, S: BuildHasher> ChildInterner<'a, T, S> {
+    /// Intern an item.
+    ///
+    /// If an equal item is already held by the local interner, a reference to it is returned.
+    /// Otherwise, if an equal item is held by the parent interner, the item is dropped
+    /// and a reference to the item held by the parent is returned.
+    /// Otherwise, the item is interned by the local interner.
+    pub fn intern(&self, item: T) -> Intern<'_, T> {
+        if let Some(interned) = self.local.get_interned(&item) {
+            return interned
+        }
+        match self.parent.get_interned(&item) {
+            Some(interned) => interned,
+            None => self.local.intern(item)
+        }
+    }
+
+    /// Returns true if an item equal to the given one is held
+    /// by this interner or by its parent.
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash
+    {
+        self.parent.contains(item) || self.local.contains(item)
+    }
+
+    /// Get a reference to the interned item equal to the given one,
+    /// held either by the parent or by this interner.
+    pub fn get_interned<Q>(&self, item: &Q) -> Option<Intern<'_, T>>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash
+    {
+        self.local.get_interned(item).or_else(|| self.parent.get_interned(item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::ChildInterner;
+    use crate::Interner;
+
+    #[test]
+    fn child_interner_test() {
+        let parent = Rc::new(Interner::new());
+        let a = parent.intern(String::from("a"));
+        let child = ChildInterner::with_parent(Rc::clone(&parent));
+        assert_eq!(child.intern(String::from("a")), a);
+        let b = child.intern(String::from("b"));
+        assert_eq!(child.intern(String::from("b")), b);
+        assert_eq!(parent.len(), 1);
+        assert_eq!(child.local().len(), 1);
+        assert!(child.contains("a"));
+        assert_eq!(child.get_interned("b"), Some(b));
+
+        // Items interned by the parent later are shared as well
+        let c = parent.intern(String::from("c"));
+        assert_eq!(child.intern(String::from("c")), c);
+        assert_eq!(child.local().len(), 1);
+
+        // Items the child already holds keep their references
+        parent.intern(String::from("b"));
+        assert_eq!(child.intern(String::from("b")), b);
+        assert_eq!(child.get_interned("b"), Some(b));
+    }
+}
@@ -0,0 +1,129 @@
+use alloc::boxed::Box;
+use core::{borrow::Borrow, cell::RefCell, hash::{BuildHasher, Hash}, marker::PhantomData};
+
+use hashbrown::HashSet;
+
+use crate::{DefaultHashBuilder, Intern};
+
+/// An interner which boxes every item separately.
+///
+/// The boxes are held directly in a hash set, which both deduplicates the items
+/// and keeps them in place, as moving a box doesn't move the item it points to.
+/// There are no holders nor a separate index, at the cost of one allocation per item:
+///
+/// ```
+/// use libintern::FastInterner;
+///
+/// let interner = FastInterner::new();
+/// let a = interner.intern(String::from("a"));
+/// assert_eq!(interner.intern(String::from("a")), a);
+/// assert_eq!(interner.get_interned("a"), Some(a));
+/// ```
+pub struct FastInterner<'a, T: 'a, S = DefaultHashBuilder> {
+    items: RefCell<HashSet<Box<T>, S>>,
+    _ph: PhantomData<&'a T>
+}
+
+// SAFETY: The interner uniquely owns the boxed items
+// and it can only be sent while none of them are borrowed
+unsafe impl<'a, T: 'a + Send, S: Send> Send for FastInterner<'a, T, S> {}
+
+impl<'a, T: 'a> FastInterner<'a, T> {
+    pub fn new() -> Self {
+        Self::with_hasher(DefaultHashBuilder::default())
+    }
+}
+
+impl<'a, T: 'a, S> FastInterner<'a, T, S> {
+    /// Create an interner which uses the given hasher to hash the items.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self { items: RefCell::new(HashSet::with_hasher(hash_builder)), _ph: PhantomData }
+    }
+
+    /// The number of distinct items held by this interner.
+    pub fn len(&self) -> usize {
+        self.items.borrow().len()
+    }
+
+    /// Returns true if no items are held by this interner.
+    pub fn is_empty(&self) -> bool {
+        self.items.borrow().is_empty()
+    }
+}
+
+impl<'a, T: 'a + Eq + Hash, S: BuildHasher> FastInterner<'a, T, S> {
+    /// Intern an item.
+    ///
+    /// This item is dropped if an item equal to this one is already interned,
+    /// in which case a reference to the already interned item is returned instead.
+    pub fn intern(&self, item: T) -> Intern<'_, T> {
+        let mut items = self.items.borrow_mut();
+        let reference: *const T = match items.get(&item) {
+            Some(interned) => &**interned,
+            None => {
+                let interned = Box::new(item);
+                let reference: *const T = &*interned;
+                items.insert(interned);
+                reference
+            }
+        };
+        // SAFETY: The returned reference borrows the interner, and the boxed items
+        // are never mutated nor dropped through a shared reference.
+        // Rehashing the set only moves the boxes, not the items they point to
+        unsafe { Intern::from_raw(reference) }
+    }
+
+    /// Get a reference to an already interned item, without interning it.
+    ///
+    /// The item can be given by any borrowed form of it.
+    pub fn get_interned<Q>(&self, item: &Q) -> Option<Intern<'_, T>>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash
+    {
+        let items = self.items.borrow();
+        let reference: *const T = &**items.get(&Lookup(item))?;
+        // SAFETY: see `intern`
+        Some(unsafe { Intern::from_raw(reference) })
+    }
+}
+
+impl<'a, T: 'a, S: Default> Default for FastInterner<'a, T, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+/// A borrowed form of an item, used to look up the held items.
+///
+/// `Box<T>` only borrows as `T`, not as every borrowed form of `T`.
+#[derive(Hash)]
+struct Lookup<'q, Q: ?Sized>(&'q Q);
+
+impl<'q, Q: ?Sized + Eq, T: Borrow<Q>> hashbrown::Equivalent<Box<T>> for Lookup<'q, Q> {
+    fn equivalent(&self, key: &Box<T>) -> bool {
+        self.0 == (**key).borrow()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FastInterner;
+
+    #[test]
+    fn fast_interner_test() {
+        let int = FastInterner::new();
+        let a = int.intern(String::from("a"));
+        let b = int.intern(String::from("b"));
+        // Grow the set, which moves the boxes around
+        for i in 0..1000 {
+            int.intern(i.to_string());
+        }
+        assert_eq!(int.intern(String::from("a")), a);
+        assert_ne!(a, b);
+        assert_eq!(*a, "a");
+        assert_eq!(int.len(), 1002);
+        assert_eq!(int.get_interned("b"), Some(b));
+        assert_eq!(int.get_interned("c"), None);
+    }
+}
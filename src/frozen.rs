@@ -0,0 +1,148 @@
+use core::{fmt, hash::BuildHasher};
+
+use hashbrown::HashTable;
+
+use crate::{DefaultHashBuilder, Symbol};
+
+/// A read-only interner of strings stored in a single byte buffer.
+///
+/// This is the read side of a persisted string interner: the strings are stored
+/// back to back in the buffer, e.g. a memory-mapped file, and the offsets table
+/// has the start of every string followed by the end of the last one.
+/// The string with symbol `i` is `bytes[offsets[i]..offsets[i + 1]]`.
+/// Nothing is allocated per string, only a lookup table of the symbols is built:
+///
+/// ```
+/// use libintern::{FrozenInterner, Symbol};
+///
+/// let interner = FrozenInterner::new(b"fnletmatch", &[0, 2, 5, 10]).unwrap();
+/// assert_eq!(interner.resolve(Symbol(1)), "let");
+/// assert_eq!(interner.get("match"), Some(Symbol(2)));
+/// assert_eq!(interner.get("if"), None);
+/// ```
+///
+/// The strings are expected to be distinct, if they aren't,
+/// [`get`](Self::get) returns the symbol of the first equal one.
+pub struct FrozenInterner<'a, S = DefaultHashBuilder> {
+    bytes: &'a [u8],
+    offsets: &'a [u32],
+    /// The symbols of the strings, keyed by the hashes of the strings
+    index: HashTable<u32>,
+    hash_builder: S
+}
+
+/// The error returned when a [`FrozenInterner`] is created from invalid data.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InvalidFrozenData {
+    /// The symbol of the string which is invalid
+    pub symbol: Symbol
+}
+
+impl fmt::Display for InvalidFrozenData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the string with symbol {} is out of bounds or isn't valid UTF-8", self.symbol.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidFrozenData {}
+
+impl<'a> FrozenInterner<'a> {
+    /// Create an interner of the strings in `bytes` delimited by `offsets`.
+    ///
+    /// Fails if any of the strings is out of bounds of the buffer,
+    /// ends before it starts, or isn't valid UTF-8.
+    pub fn new(bytes: &'a [u8], offsets: &'a [u32]) -> Result<Self, InvalidFrozenData> {
+        Self::with_hasher(bytes, offsets, DefaultHashBuilder::default())
+    }
+}
+
+impl<'a, S: BuildHasher> FrozenInterner<'a, S> {
+    /// Create an interner of the strings in `bytes` delimited by `offsets`,
+    /// which uses the given hasher to hash the strings.
+    ///
+    /// Fails like [`new`](FrozenInterner::new).
+    pub fn with_hasher(bytes: &'a [u8], offsets: &'a [u32], hash_builder: S) -> Result<Self, InvalidFrozenData> {
+        let len = offsets.len().saturating_sub(1);
+        let mut index = HashTable::with_capacity(len);
+        for (id, bounds) in (0..).zip(offsets.windows(2)) {
+            let invalid = InvalidFrozenData { symbol: Symbol(id) };
+            let string = bytes.get(bounds[0] as usize..bounds[1] as usize).ok_or(invalid)?;
+            let string = core::str::from_utf8(string).map_err(|_| invalid)?;
+            index.insert_unique(hash_builder.hash_one(string), id, |&other| {
+                // SAFETY: the strings before this one were already validated
+                hash_builder.hash_one(unsafe { Self::string_at(bytes, offsets, other) })
+            });
+        }
+        Ok(Self { bytes, offsets, index, hash_builder })
+    }
+
+    /// Get the symbol of the given string, if it's held by this interner.
+    pub fn get(&self, s: &str) -> Option<Symbol> {
+        let hash = self.hash_builder.hash_one(s);
+        // SAFETY: all the strings were validated on creation
+        let id = self.index.find(hash, |&id| unsafe { Self::string_at(self.bytes, self.offsets, id) } == s)?;
+        Some(Symbol(*id))
+    }
+}
+
+impl<'a, S> FrozenInterner<'a, S> {
+    /// The number of strings held by this interner.
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// Returns true if no strings are held by this interner.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the string with the given symbol.
+    ///
+    /// The string points into the buffer, so it outlives the interner.
+    ///
+    /// # Panics
+    /// Panics if the symbol is out of range for this interner.
+    pub fn resolve(&self, symbol: Symbol) -> &'a str {
+        assert!(
+            (symbol.0 as usize) < self.len(),
+            "symbol {} is out of range for an interner of {} items", symbol.0, self.len()
+        );
+        // SAFETY: all the strings were validated on creation
+        unsafe { Self::string_at(self.bytes, self.offsets, symbol.0) }
+    }
+
+    /// Get the string with the given id.
+    ///
+    /// # Safety
+    /// The string must be in bounds and valid UTF-8.
+    unsafe fn string_at(bytes: &'a [u8], offsets: &[u32], id: u32) -> &'a str {
+        let id = id as usize;
+        core::str::from_utf8_unchecked(bytes.get_unchecked(offsets[id] as usize..offsets[id + 1] as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FrozenInterner, InvalidFrozenData};
+    use crate::Symbol;
+
+    #[test]
+    fn frozen_interner_test() {
+        let bytes = "helloworldçà".as_bytes();
+        let int = FrozenInterner::new(bytes, &[0, 5, 10, 10, 14]).unwrap();
+        assert_eq!(int.len(), 4);
+        assert_eq!(int.resolve(Symbol(0)), "hello");
+        assert_eq!(int.resolve(Symbol(2)), "");
+        assert_eq!(int.resolve(Symbol(3)), "çà");
+        assert_eq!(int.get("world"), Some(Symbol(1)));
+        assert_eq!(int.get(""), Some(Symbol(2)));
+        assert_eq!(int.get("hell"), None);
+
+        // Out of bounds, backwards, and splitting a character
+        assert_eq!(FrozenInterner::new(bytes, &[0, 15]).err(), Some(InvalidFrozenData { symbol: Symbol(0) }));
+        assert_eq!(FrozenInterner::new(bytes, &[0, 5, 2]).err(), Some(InvalidFrozenData { symbol: Symbol(1) }));
+        assert!(FrozenInterner::new(bytes, &[0, 11]).is_err());
+        assert!(FrozenInterner::new(bytes, &[]).unwrap().is_empty());
+    }
+}
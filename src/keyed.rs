@@ -0,0 +1,135 @@
+use core::{borrow::Borrow, cell::RefCell, hash::{BuildHasher, Hash}, marker::PhantomData};
+
+use hashbrown::HashMap;
+
+use crate::{DefaultHashBuilder, Intern, InternerState, Symbol, BEGIN_INTERNER_CAPACITY};
+
+/// An interner which deduplicates the items by a separate key.
+///
+/// Unlike [`Interner`](crate::Interner), the items don't have to implement `Eq` nor `Hash`,
+/// only the keys do. This makes it possible to intern e.g. trait objects:
+///
+/// ```
+/// use libintern::KeyedInterner;
+///
+/// let interner: KeyedInterner<&str, Box<dyn Fn(i32) -> i32>> = KeyedInterner::new();
+/// let double = interner.intern_by_key(Box::new(|x| x * 2), "double");
+/// let other = interner.intern_by_key(Box::new(|x| x + 2), "double");
+/// assert!(double == other);
+/// assert_eq!(other(4), 8);
+/// ```
+pub struct KeyedInterner<'a, K, T: 'a, S = DefaultHashBuilder> {
+    state: RefCell<KeyedInternerState<K, T, S>>,
+    _ph: PhantomData<&'a T>
+}
+
+struct KeyedInternerState<K, T, S> {
+    /// The held items, the index of which is never used
+    items: InternerState<T>,
+    /// Maps the keys to the symbols of the items
+    keys: HashMap<K, Symbol, S>
+}
+
+// SAFETY: The pointers in the symbol table only ever point into the holders
+// owned by this interner, see the implementation for [`Interner`](crate::Interner)
+unsafe impl<'a, K: Send, T: 'a + Send, S: Send> Send for KeyedInterner<'a, K, T, S> {}
+
+impl<'a, K, T: 'a> KeyedInterner<'a, K, T> {
+    pub fn new() -> Self {
+        Self::with_hasher(DefaultHashBuilder::default())
+    }
+}
+
+impl<'a, K, T: 'a, S> KeyedInterner<'a, K, T, S> {
+    /// Create an interner which uses the given hasher to hash the keys.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            state: RefCell::new(KeyedInternerState {
+                items: InternerState::new(BEGIN_INTERNER_CAPACITY, None),
+                keys: HashMap::with_hasher(hash_builder)
+            }),
+            _ph: PhantomData
+        }
+    }
+
+    /// The number of distinct items held by this interner.
+    pub fn len(&self) -> usize {
+        self.state.borrow().items.symbols.len()
+    }
+
+    /// Returns true if no items are held by this interner.
+    pub fn is_empty(&self) -> bool {
+        self.state.borrow().items.symbols.is_empty()
+    }
+
+    /// Turn a symbol held by this interner into a reference to the item.
+    fn resolve(&self, symbol: Symbol) -> Intern<'_, T> {
+        let reference = self.state.borrow().items.symbols[symbol.0 as usize];
+        // SAFETY: The returned reference borrows the interner and the held items are
+        // never mutated nor moved through a shared reference, see [`Interner::resolve`]
+        unsafe { Intern::from_raw(reference) }
+    }
+}
+
+impl<'a, K: Eq + Hash, T: 'a, S: BuildHasher> KeyedInterner<'a, K, T, S> {
+    /// Intern an item under the given key.
+    ///
+    /// If an item with an equal key is already interned, both the item and the key
+    /// are dropped and a reference to the already interned item is returned instead.
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` distinct items would be interned.
+    pub fn intern_by_key(&self, item: T, key: K) -> Intern<'_, T> {
+        let mut state = self.state.borrow_mut();
+        let symbol = match state.keys.get(&key) {
+            Some(&symbol) => symbol,
+            None => {
+                let symbol = state.items.hold_unindexed_symbol(item);
+                state.keys.insert(key, symbol);
+                symbol
+            }
+        };
+        drop(state);
+        self.resolve(symbol)
+    }
+
+    /// Get a reference to the item interned under the given key.
+    ///
+    /// The key can be given by any borrowed form of it.
+    pub fn get_by_key<Q>(&self, key: &Q) -> Option<Intern<'_, T>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash
+    {
+        let symbol = *self.state.borrow().keys.get(key)?;
+        Some(self.resolve(symbol))
+    }
+}
+
+impl<'a, K, T: 'a, S: Default> Default for KeyedInterner<'a, K, T, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeyedInterner;
+
+    #[test]
+    fn keyed_interner_test() {
+        // The items don't implement Eq
+        struct Payload(f64);
+
+        let int = KeyedInterner::new();
+        let a = int.intern_by_key(Payload(1.0), String::from("a"));
+        let b = int.intern_by_key(Payload(2.0), String::from("b"));
+        let other_a = int.intern_by_key(Payload(3.0), String::from("a"));
+        assert!(a == other_a);
+        assert!(a != b);
+        assert_eq!((*other_a).0, 1.0);
+        assert_eq!(int.len(), 2);
+        assert!(int.get_by_key("b") == Some(b));
+        assert!(int.get_by_key("c").is_none());
+    }
+}
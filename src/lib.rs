@@ -1,73 +1,208 @@
 #![feature(negative_impls)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-use std::{borrow::Borrow, fmt::{Debug, Display}, hash::Hash, marker::PhantomData, ops::Deref, pin::Pin};
+extern crate alloc;
+
+mod arc;
+mod bounded;
+mod child;
+mod fast;
+mod frozen;
+mod keyed;
+mod normalizing;
+mod projected;
+mod rc;
+mod streaming;
+mod string;
+#[cfg(feature = "std")]
+mod sync;
+#[cfg(feature = "serde")]
+mod serde_impl;
+
+pub use arc::ArcInterner;
+pub use bounded::{BoundedInterner, InternFull};
+pub use child::ChildInterner;
+pub use fast::FastInterner;
+pub use frozen::{FrozenInterner, InvalidFrozenData};
+pub use keyed::KeyedInterner;
+pub use normalizing::NormalizingInterner;
+pub use projected::{Identity, InternKey, ProjectedInterner};
+pub use rc::RcInterner;
+pub use streaming::{BatchStats, StreamingInterner};
+pub use string::StringInterner;
+#[cfg(feature = "std")]
+pub use sync::SyncInterner;
+
+use alloc::{borrow::{Cow, ToOwned}, boxed::Box, format, string::String, sync::Arc, vec, vec::Vec};
+use core::{borrow::Borrow, cell::RefCell, convert::TryFrom, fmt::{Debug, Display}, hash::{BuildHasher, BuildHasherDefault, Hash, Hasher}, marker::PhantomData, ops::{Deref, Index}, pin::Pin};
+use hashbrown::HashMap;
+
+/// The hasher used by an [`Interner`] by default.
+///
+/// This is the default hasher of [`hashbrown`], no matter whether the `std` feature
+/// is enabled, so enabling the feature doesn't change the type of an [`Interner`].
+/// It's fast and randomly seeded, but unlike the `RandomState` of the standard library,
+/// it isn't designed to resist HashDoS attacks. To intern untrusted input,
+/// pass `std::collections::hash_map::RandomState` to [`Interner::with_hasher`].
+pub type DefaultHashBuilder = hashbrown::DefaultHashBuilder;
 
 /// The interner.
 ///
 /// An interner is a structure which uniquely owns the interned items,
 /// and provides shared immutable references to those items.
-pub struct Interner<'a, T: 'a + Eq> {
-    /// A list of holders of the items
-    holders: Vec<InternedItemHolder<T>>,
+///
+/// The references borrow the interner, so the interner can't be dropped, moved
+/// or mutably borrowed (e.g. [`clear`](Self::clear)ed) while any of them are alive.
+/// Interning itself only needs a shared reference, which is what allows
+/// keeping the references around while interning more items.
+///
+/// The hashes of the items are computed by the `S` hasher,
+/// which can be swapped for a faster one via [`with_hasher`](Self::with_hasher).
+/// The hasher only affects the lookups: the [`Symbol`]s are assigned in insertion order
+/// and [`iter`](Self::iter) and [`to_vec`](Self::to_vec) yield the items in that order,
+/// so interning the same sequence of items always produces the same symbol table,
+/// even with a randomly seeded hasher.
+///
+/// How the holders of the items grow is set by the `P` [`InternerPolicy`].
+pub struct Interner<'a, T: 'a + Eq, S = DefaultHashBuilder, P = DefaultPolicy> {
+    /// The held items and the structures to look them up
+    state: RefCell<InternerState<T, P>>,
+    /// The hasher used to compute the hashes in the index
+    hash_builder: S,
     _ph: PhantomData<&'a T>
 }
 
-impl<'a, T> !Sync for Interner<'a, T> {}
+impl<'a, T, S, P> !Sync for Interner<'a, T, S, P> {}
+
+// SAFETY: The pointers in the symbol table only ever point into the holders
+// and the boxes owned by this interner, so sending the interner to another thread
+// is exactly as safe as sending the items themselves.
+// The only exception are the static and shared items, which may also be used
+// by the current thread, but those can only be interned if they are Sync
+unsafe impl<'a, T: 'a + Eq + Send, S: Send, P> Send for Interner<'a, T, S, P> {}
 
 /// The capacity of the first InternedItemHolder
 const BEGIN_INTERNER_CAPACITY: usize = 32;
 /// By how much every next interner's capacity changes
 const INTERNER_CAPACITY_DELTA: f32 = 1.5;
+/// Up to how many items are looked up by a linear scan instead of the hash index
+const LINEAR_LOOKUP_THRESHOLD: usize = 16;
 
-impl<'a, T: 'a + Eq> Interner<'a, T> {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { 
-            holders: vec![
-                InternedItemHolder::new(BEGIN_INTERNER_CAPACITY)],
-            _ph: PhantomData 
-        }
-    }
-
-    /// Intern an item.
+/// The mutable state of an [`Interner`].
+///
+/// Through a shared reference to the interner, new items are only ever added,
+/// the items which are already held are NEVER mutated nor moved.
+struct InternerState<T, P = DefaultPolicy> {
+    /// A list of holders of the items
+    holders: Vec<InternedItemHolder<T>>,
+    /// Empty holders kept by [`Interner::reset`], reused before allocating new ones.
     ///
-    /// This consumes the item by adding it to the intern-list and returns a reference to it.
-    /// It also extends the lifetime of the item to match the lifetime of this interner.
+    /// The next one to be reused is the last one
+    spare_holders: Vec<InternedItemHolder<T>>,
+    /// Pointers to the held items, in insertion order.
     ///
-    /// This item is dropped if an item equal to this one is already interned,
-    /// in which case a reference to the already interned item is returned instead.
-    pub fn intern(&mut self, item: T) -> Intern<'a, T> {
-        // Look whether an item equal to this one already exists
-        let mut result = None;
-        for holder in &self.holders {
-            for h_item in &holder.items {
-                if &item == h_item {
-                    result = Some(h_item);
-                    break
-                }
-            }
+    /// The position of an item in this table is its [`Symbol`].
+    /// The items are either owned by the holders or external, see `external`
+    symbols: Vec<*const T>,
+    /// The symbols of the items which are not owned by the holders, in insertion order.
+    ///
+    /// The symbol table points to those items directly
+    external: Vec<(Symbol, ExternalItem<T>)>,
+    /// An index of the held items.
+    ///
+    /// Maps the hash of an item to the symbols of all held items with that hash,
+    /// so that only a small bucket of candidates has to be compared on lookup.
+    /// It's only built once `linear_threshold` items are held, see `hashes`
+    index: HashMap<u64, Vec<Symbol>, BuildHasherDefault<PrehashedHasher>>,
+    /// The hashes of the held items, in symbol order, while the index isn't built yet.
+    ///
+    /// While only a few items are held, scanning their hashes is faster than using the index
+    hashes: Option<Vec<u64>>,
+    /// How many items have to be held for the index to be built
+    linear_threshold: usize,
+    /// Whether the new items are boxed instead of being moved into the holders,
+    /// see [`Interner::new_boxed`]
+    boxed: bool,
+    /// By how much every next holder's capacity changes, if set by [`InternerBuilder::growth_factor`].
+    ///
+    /// Otherwise the growth of the policy `P` is used
+    growth_factor: Option<f32>,
+    /// How many items are expected to be interned in total, see [`Interner::set_expected_items`]
+    expected_items: usize,
+    /// How many times interning found an equal item, see [`Interner::hits`]
+    hits: u64,
+    /// How many new items were interned, see [`Interner::misses`]
+    misses: u64,
+    /// Called with the capacity of every newly added holder, see [`Interner::on_grow`]
+    on_grow: Option<Box<dyn FnMut(usize) + Send>>,
+    _policy: PhantomData<fn() -> P>
+}
+
+/// An item which is not owned by the holders.
+enum ExternalItem<T> {
+    /// A static item, which lives for the rest of the program
+    Static,
+    /// A shared item, which is kept alive by holding one of its references
+    Shared(Arc<T>),
+    /// A boxed item, which is owned by the interner but kept in its own allocation
+    Boxed(Box<T>)
+}
+
+impl<T: Clone> Clone for ExternalItem<T> {
+    /// Share the static and shared items, copy the boxed ones.
+    fn clone(&self) -> Self {
+        match self {
+            ExternalItem::Static => ExternalItem::Static,
+            ExternalItem::Shared(item) => ExternalItem::Shared(Arc::clone(item)),
+            ExternalItem::Boxed(item) => ExternalItem::Boxed(item.clone())
         }
-        // If the new item is unique, add it to the holder
-        if result.is_none() {
-            self.hold_new_item(item);
-            result = Some(
-                // See documentation for [`hold_new_item`]
-                self.holders.last().unwrap().items.last().unwrap()
-            )
+    }
+}
+
+/// A hasher for the keys of the index.
+///
+/// The keys are already hashes computed by the hasher of the interner,
+/// so hashing them again would be a waste of time.
+#[derive(Default)]
+struct PrehashedHasher(u64);
+
+impl Hasher for PrehashedHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = self.0.rotate_left(8) ^ u64::from(byte);
         }
-        let reference = result.unwrap();
-        unsafe { self.transmute_held_item(reference) }
     }
 
-    pub fn contains(&self, item: &T) -> bool {
-        for holder in &self.holders {
-            for h_item in &holder.items {
-                if item == h_item {
-                    return true
-                }
-            }
+    fn write_u64(&mut self, hash: u64) {
+        self.0 = hash;
+    }
+}
+
+impl<T, P: InternerPolicy> InternerState<T, P> {
+    /// Create an empty state whose first holder has room for `capacity` items.
+    fn new(capacity: usize, growth_factor: Option<f32>) -> Self {
+        const { assert!(P::GROWTH > 1.0, "the growth factor must be greater than 1.0") };
+        Self {
+            holders: vec![
+                InternedItemHolder::new(capacity.max(1))],
+            spare_holders: Vec::new(),
+            symbols: Vec::new(),
+            external: Vec::new(),
+            index: HashMap::default(),
+            hashes: Some(Vec::new()),
+            linear_threshold: LINEAR_LOOKUP_THRESHOLD,
+            boxed: false,
+            growth_factor,
+            expected_items: 0,
+            hits: 0,
+            misses: 0,
+            on_grow: None,
+            _policy: PhantomData
         }
-        false
     }
 
     /// Hold a new item.
@@ -78,311 +213,3962 @@ impl<'a, T: 'a + Eq> Interner<'a, T> {
         match self.holders.last_mut().unwrap().try_push(item) {
             Ok(()) => (),
             Err(item) => {
-                // The holder is full, add a new one
+                // The spare holders grow like the holders, but a holder added since the reset,
+                // e.g. by reserving, may be larger than some of them, which are of no use then
                 let last_holder_capacity = self.holders.last().unwrap().items.capacity();
-                let mut new_holder = InternedItemHolder::new(
-                    ((last_holder_capacity as f32) * INTERNER_CAPACITY_DELTA) as usize
-                );
+                while self.spare_holders.last().is_some_and(|spare| spare.items.capacity() <= last_holder_capacity) {
+                    self.spare_holders.pop();
+                }
+                if let Some(mut spare_holder) = self.spare_holders.pop() {
+                    // Reusing a holder doesn't allocate, so the growth callback isn't called
+                    spare_holder.items.push(item);
+                    self.holders.push(spare_holder);
+                    return
+                }
+                // The holder is full, add a new one, large enough for all the items
+                // which are still expected to be interned
+                let capacity = self.next_holder_capacity()
+                    .max(self.expected_items.saturating_sub(self.symbols.len()));
+                let mut new_holder = InternedItemHolder::new(capacity);
                 // Add to the holder
                 new_holder.items.push(item);
                 // Add the holder to the list of holders
-                self.holders.push(new_holder);
+                self.push_holder(new_holder);
             }
         }
     }
 
-    /// Transmute a reference to an item held by this interner
-    /// into the Intern<T> type.
-    #[inline]
-    unsafe fn transmute_held_item(&self, item: &T) -> Intern<'a, T> {
-        // SAFETY: Via the lifetime <'a>, we guarantee the interner is alive
-        // as long as the references are alive. Furthermore, the data is NEVER
-        // mutated AND only immutable references to the data exist.
-        // Therefore we uphold all guarantees and can assume safety when transmuting
-        let reference: &'a T = std::mem::transmute(item);
-        // SAFETY: I believe for the reasons stated above, this is also safe
-        let pinned_reference: Pin<&'a T> = Pin::new_unchecked(reference);
-        Intern(pinned_reference)
+    /// Add a new last holder and report it to the growth callback.
+    fn push_holder(&mut self, holder: InternedItemHolder<T>) {
+        if let Some(on_grow) = &mut self.on_grow {
+            on_grow(holder.items.capacity());
+        }
+        self.holders.push(holder);
     }
 
-    pub fn iter<'this>(&'this self) -> Iter<'this, 'a, T> {
-        Iter { interner: self, holder_id: 0, inside_holder_id: 0 }
+    /// The capacity of the holder which would be added after the last one.
+    fn next_holder_capacity(&self) -> usize {
+        let last_holder_capacity = self.holders.last().unwrap().items.capacity();
+        // Make sure the capacity always grows, even if the truncated product
+        // of a small capacity and the delta doesn't
+        let growth_factor = self.growth_factor.unwrap_or(P::GROWTH);
+        (((last_holder_capacity as f32) * growth_factor) as usize)
+            .max(last_holder_capacity + 1)
     }
-}
-
-/// A wrapper around a vector, which guarantees that
-/// the vector will never grow, thus the addresses (pointers)
-/// of (to) its items will never change
-struct InternedItemHolder<T> {
-    items: Vec<T>
-}
 
-impl<T> InternedItemHolder<T> {
-    fn new(capacity: usize) -> Self {
-        Self { items: Vec::with_capacity(capacity) }
+    /// Hold a new unique item with the given hash and assign a symbol to it.
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` distinct items would be interned.
+    fn hold_new_symbol(&mut self, hash: u64, item: T) -> Symbol {
+        if self.boxed {
+            return self.hold_boxed_symbol(hash, Box::new(item))
+        }
+        let symbol = self.hold_unindexed_symbol(item);
+        self.index_symbol(hash, symbol);
+        symbol
     }
 
-    /// Try to add an item to the holder.
+    /// Add the most recently assigned symbol, whose item has the given hash, to the index.
     ///
-    /// If there's enough space for the item, succeed and return Ok(())
-    /// If there's not enough space in the holder,
-    ///  it returns Err(the_item), to prevent dropping the value
-    fn try_push(&mut self, item: T) -> Result<(), T> {
-        if self.items.len() == self.items.capacity() {
-            Err(item)
-        } else {
-            self.items.push(item);
-            Ok(())
+    /// Builds the index from the hashes if the symbol reaches the linear threshold.
+    fn index_symbol(&mut self, hash: u64, symbol: Symbol) {
+        self.misses += 1;
+        match &mut self.hashes {
+            Some(hashes) => {
+                hashes.push(hash);
+                if hashes.len() >= self.linear_threshold {
+                    self.build_index();
+                }
+            },
+            None => self.index.entry(hash).or_default().push(symbol)
         }
     }
-}
 
-/// A reference to an interned item.
-///
-/// The main advantage of this type over just
-/// an immutable reference is that its pointer is guaranteed to be unique
-/// within a single [`Interner`]. Therefore comparisons
-/// are very cheap.
-///
-/// # Note about hashing
-///
-/// This wrapper implements [`PartialEq`] by comparing its inner pointer.
-/// In order to keep consistency, the [`Hash`] trait is also implemented
-/// by hashing the pointer, NOT the inner value. Therefore hashes
-/// of the `Intern<T>` type are different than hashes of the `T`.
-pub struct Intern<'a, T: 'a>(Pin<&'a T>);
+    /// Move the hashes of the linearly scanned items into the index.
+    fn build_index(&mut self) {
+        for (id, hash) in self.hashes.take().into_iter().flatten().enumerate() {
+            self.index.entry(hash).or_default().push(Symbol(id as u32));
+        }
+    }
 
-impl<'a, T> Clone for Intern<'a, T> {
-    fn clone(&self) -> Self {
-        Intern(self.0)
+    /// Set up how many items have to be held for the index to be built.
+    fn set_linear_threshold(&mut self, threshold: usize) {
+        self.linear_threshold = threshold;
+        if self.hashes.as_ref().is_some_and(|hashes| hashes.len() >= threshold) {
+            self.build_index();
+        }
     }
-}
 
-// We must hand implement Copy, because a T: Copy bound is added when using derive
-impl<'a, T> Copy for Intern<'a, T> {}
+    /// Forget all the hashes of the held items.
+    fn clear_index(&mut self) {
+        self.index.clear();
+        self.hashes = Some(Vec::new());
+        if self.linear_threshold == 0 {
+            self.build_index();
+        }
+    }
 
-// Get reference to the inner item
-impl<'a, T> AsRef<T> for Intern<'a, T> {
-    fn as_ref(&self) -> &T {
-        self.0.get_ref()
+    /// The symbols of all held items with the given hash.
+    fn bucket(&self, hash: u64) -> Vec<Symbol> {
+        match &self.hashes {
+            Some(hashes) => hashes.iter()
+                .enumerate()
+                .filter(|&(_, &other)| other == hash)
+                .map(|(id, _)| Symbol(id as u32))
+                .collect(),
+            None => self.index.get(&hash).cloned().unwrap_or_default()
+        }
     }
-}
 
-impl<'a, T> Borrow<T> for Intern<'a, T> {
-    fn borrow(&self) -> &T {
-        self.0.get_ref()
+    /// Hold a new item and assign a symbol to it, without adding it to the index.
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` items would be held.
+    fn hold_unindexed_symbol(&mut self, item: T) -> Symbol {
+        self.hold_new_item(item);
+        // See documentation for [`hold_new_item`]
+        let last_holder = self.holders.last().unwrap();
+        last_holder.debug_assert_not_reallocated();
+        let reference: *const T = last_holder.items.last().unwrap();
+        self.push_symbol(reference)
     }
-}
 
-impl<'a, T> Deref for Intern<'a, T> {
-    type Target = T;
+    /// Hold a new unique static item with the given hash and assign a symbol to it.
+    ///
+    /// The item isn't copied into the holders, it's referenced directly,
+    /// so it must live for the rest of the program.
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` distinct items would be interned.
+    fn hold_static_symbol(&mut self, hash: u64, item: *const T) -> Symbol {
+        self.hold_external_symbol(hash, item, ExternalItem::Static)
+    }
 
-    fn deref(&self) -> &Self::Target {
-        self.as_ref()
+    /// Hold a new unique shared item with the given hash and assign a symbol to it.
+    ///
+    /// The item isn't copied into the holders, the interner keeps it alive
+    /// by holding one of its references.
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` distinct items would be interned.
+    fn hold_shared_symbol(&mut self, hash: u64, item: Arc<T>) -> Symbol {
+        let reference: *const T = &*item;
+        self.hold_external_symbol(hash, reference, ExternalItem::Shared(item))
     }
-}
 
-// Implement Debug if the item implements Debug
-impl<'a, T: Debug> Debug for Intern<'a, T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.as_ref().fmt(f)
+    /// Hold a new unique boxed item with the given hash and assign a symbol to it.
+    ///
+    /// The item isn't moved into the holders, the interner keeps the box instead.
+    /// Moving the box doesn't move the item, so its address is stable.
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` distinct items would be interned.
+    fn hold_boxed_symbol(&mut self, hash: u64, item: Box<T>) -> Symbol {
+        let reference: *const T = &*item;
+        self.hold_external_symbol(hash, reference, ExternalItem::Boxed(item))
     }
-}
 
-// Implement Display if the item implements Display
-impl<'a, T: Display> Display for Intern<'a, T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.as_ref().fmt(f)
+    fn hold_external_symbol(&mut self, hash: u64, reference: *const T, item: ExternalItem<T>) -> Symbol {
+        let symbol = self.push_symbol(reference);
+        self.external.push((symbol, item));
+        self.index_symbol(hash, symbol);
+        symbol
     }
-}
 
-// Implement PartialEq
-// 
-// Because we can guarantee that if the item is the same,
-// the item's place in memory, therefore the pointer is the same,
-// we can just compare values of the pointers, not the items themselves 
-impl<'a, T> PartialEq for Intern<'a, T> {
-    fn eq(&self, other: &Self) -> bool {
-        std::ptr::eq(self.as_ref() as *const _, other.as_ref() as *const _)
+    /// Assign the next symbol to a held item.
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` items would be held.
+    fn push_symbol(&mut self, reference: *const T) -> Symbol {
+        let symbol = Symbol(u32::try_from(self.symbols.len()).expect("too many interned items"));
+        self.symbols.push(reference);
+        symbol
     }
-}
 
-impl<'a, T> Eq for Intern<'a, T> {}
+    /// Returns true if the address is the address of a held item.
+    fn holds_address(&self, addr: usize) -> bool {
+        let size = core::mem::size_of::<T>();
+        let is_external = self.external.iter()
+            .any(|(symbol, _)| self.symbols[symbol.0 as usize] as *const () as usize == addr);
+        is_external || self.holders.iter().any(|holder| {
+            let start = holder.items.as_ptr() as usize;
+            if size == 0 {
+                return addr == start && !holder.items.is_empty()
+            }
+            addr >= start && addr < start + holder.items.len() * size && (addr - start).is_multiple_of(size)
+        })
+    }
 
-// Implement Hash
-// 
-/// To keep consistency with [`PartialEq`], we hash the pointer, not the value
-impl<'a, T> Hash for Intern<'a, T> {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        std::ptr::hash(self.0.get_ref(), state)
+    /// Find the symbol of a held item equal to the item being interned, whose hash is `hash`.
+    ///
+    /// Works like `find_held`, but counts the hits.
+    fn find_interned<Q>(&mut self, hash: u64, item: &Q) -> Option<Symbol>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq
+    {
+        let found = self.find_held(hash, item);
+        if found.is_some() {
+            self.hits += 1;
+        }
+        found
     }
-}
 
-// Implement PartialOrd and Ord if the item implements it
-impl<'a, T: PartialOrd> PartialOrd for Intern<'a, T> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.0.partial_cmp(&other.0)
+    /// Find the symbol of a held item equal to the given item, whose hash is `hash`.
+    fn find_held<Q>(&self, hash: u64, item: &Q) -> Option<Symbol>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq
+    {
+        // SAFETY: the symbol table only contains pointers to the held items
+        let is_equal = |symbol: &Symbol| unsafe { &*self.symbols[symbol.0 as usize] }.borrow() == item;
+        match &self.hashes {
+            Some(hashes) => hashes.iter()
+                .zip(0..)
+                .map(|(&other, id)| (other, Symbol(id)))
+                .find(|(other, symbol)| *other == hash && is_equal(symbol))
+                .map(|(_, symbol)| symbol),
+            None => self.index.get(&hash)?
+                .iter()
+                .copied()
+                .find(is_equal)
+        }
     }
 }
 
-impl<'a, T: Ord> Ord for Intern<'a, T> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.0.cmp(&other.0)
+impl<'a, T: 'a + Eq> Interner<'a, T> {
+    pub fn new() -> Self {
+        Self::with_capacity(BEGIN_INTERNER_CAPACITY)
     }
-}
 
-impl<'a, T> Intern<'a, T> {
-    /// Create a new [`Intern`] type from a raw pointer.
+    /// Create an interner whose first holder has room for `capacity` items.
     ///
-    /// # Safety
-    /// The caller must guarantee that the pointed value is in fact
-    /// owned by an [`Interner`] which is still in scope (i.e. not dropped)
-    /// and that the pointer will not be mutated and/or moved.
-    pub unsafe fn from_raw(ptr: *const T) -> Self {
-        Intern(Pin::new_unchecked(ptr.as_ref().unwrap()))
+    /// The first holder always has room for at least one item.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, DefaultHashBuilder::default())
+    }
+
+    /// Create an interner which boxes every item separately.
+    ///
+    /// By default, the items are moved into holders, large vectors which never grow.
+    /// That takes few allocations, but the unused room of the last holder is wasted,
+    /// which is up to a third of the memory with the default growth.
+    /// A boxed interner allocates every distinct item on its own instead,
+    /// which wastes no room, but takes one allocation per item.
+    /// Both work exactly the same otherwise.
+    ///
+    /// ```
+    /// use libintern::Interner;
+    ///
+    /// let interner = Interner::new_boxed();
+    /// let a = interner.intern(String::from("a"));
+    /// assert_eq!(interner.intern(String::from("a")), a);
+    /// ```
+    pub fn new_boxed() -> Self {
+        InternerBuilder::new().boxed().build()
     }
+
 }
 
-pub struct Iter<'a, 'intern, T: std::cmp::Eq> {
-    interner: &'a Interner<'intern, T>,
-    holder_id: usize,
-    inside_holder_id: usize
+impl<'a, T: 'a + Eq, P: InternerPolicy> Interner<'a, T, DefaultHashBuilder, P> {
+    /// Create an interner which grows according to the policy `P`.
+    ///
+    /// ```
+    /// use libintern::{DefaultHashBuilder, Interner, InternerPolicy};
+    ///
+    /// struct Small;
+    ///
+    /// impl InternerPolicy for Small {
+    ///     const BASE: usize = 4;
+    ///     const GROWTH: f32 = 2.0;
+    /// }
+    ///
+    /// let interner: Interner<char, DefaultHashBuilder, Small> = Interner::with_policy();
+    /// assert_eq!(interner.capacity(), 4);
+    /// ```
+    pub fn with_policy() -> Self {
+        Self::with_policy_and_hasher(DefaultHashBuilder::default())
+    }
 }
 
-impl<'a, 'intern, T: std::cmp::Eq> Iterator for Iter<'a, 'intern, T> {
-    type Item = Intern<'intern, T>;
+impl<'a, T: 'a + Eq, S> Interner<'a, T, S> {
+    /// Create an interner which uses the given hasher to hash the items.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self::with_capacity_and_hasher(BEGIN_INTERNER_CAPACITY, hash_builder)
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let holder = self.interner.holders.get(self.holder_id)?;
-        let item = &holder.items[self.inside_holder_id];
-        if self.inside_holder_id == holder.items.len() - 1 {
-            //if this is the last item inside this holder
-            self.holder_id += 1;
-            self.inside_holder_id = 0;
-        } else {
-            self.inside_holder_id += 1;
-        }
-        Some(unsafe { self.interner.transmute_held_item(item) })
+    /// Create an interner whose first holder has room for `capacity` items
+    /// and which uses the given hasher to hash the items.
+    ///
+    /// The first holder always has room for at least one item.
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        Self::with_config(capacity, None, hash_builder)
     }
 }
 
+impl<'a, T: 'a + Eq, S, P: InternerPolicy> Interner<'a, T, S, P> {
+    /// Create an interner which grows according to the policy `P`
+    /// and which uses the given hasher to hash the items.
+    pub fn with_policy_and_hasher(hash_builder: S) -> Self {
+        Self::with_config(P::BASE, None, hash_builder)
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::{InternedItemHolder, Interner};
+    /// Create an interner with the given capacity of the first holder
+    /// and growth factor of the next holders, if it's not the one of the policy.
+    fn with_config(capacity: usize, growth_factor: Option<f32>, hash_builder: S) -> Self {
+        Self { 
+            state: RefCell::new(InternerState::new(capacity, growth_factor)),
+            hash_builder,
+            _ph: PhantomData 
+        }
+    }
 
-    #[test]
-    fn interned_item_holder_test() {
-        let mut holder = InternedItemHolder::new(4); // size four
+    /// The number of distinct items held by this interner.
+    pub fn len(&self) -> usize {
+        self.state.borrow().symbols.len()
+    }
 
-        // Add an item
-        assert!(holder.try_push('a').is_ok());
-        assert!(holder.items.len() == 1);
-        assert!(holder.items.capacity() == 4);
-        // Save the address of the item
-        let first_item_address = holder.items.get(0).unwrap() as *const _ as usize;
+    /// Returns true if no items are held by this interner.
+    pub fn is_empty(&self) -> bool {
+        self.state.borrow().symbols.is_empty()
+    }
 
-        // Add another item
-        assert!(holder.try_push('b').is_ok());
-        assert!(holder.items.len() == 2);
-        assert!(holder.items.capacity() == 4);
-        // Make sure the address of the first one didn't change
-        assert_eq!(
-            holder.items.get(0).unwrap() as *const _ as usize,
-            first_item_address
-        );
-        let second_item_address = holder.items.get(1).unwrap() as *const _ as usize;
+    /// Iterate over the symbols of all the interned items, in insertion order.
+    ///
+    /// As the symbols are dense, this is the same as iterating over `0..len()`.
+    /// Items interned after this method is called are not yielded.
+    pub fn symbols(&self) -> impl Iterator<Item = Symbol> {
+        (0..self.len()).map(|id| Symbol(id as u32))
+    }
 
-        // Add two more items
-        assert!(holder.try_push('c').is_ok());
-        assert!(holder.try_push('d').is_ok());
-        assert!(holder.items.len() == 4);
-        assert!(holder.items.capacity() == 4);
-        // Make sure the addresses didn't change
-        assert_eq!(
-            holder.items.get(0).unwrap() as *const _ as usize,
-            first_item_address
-        );
-        assert_eq!(
-            holder.items.get(1).unwrap() as *const _ as usize,
-            second_item_address
-        );
+    /// Get references to all the interned items, in insertion order.
+    ///
+    /// The position of an item in the returned vector is its [`Symbol`].
+    pub fn to_vec(&self) -> Vec<&T> {
+        self.state.borrow().symbols.iter()
+            // SAFETY: the symbol table only contains pointers to the held items,
+            // which are never mutated nor moved while the interner is borrowed
+            .map(|&reference| unsafe { &*reference })
+            .collect()
+    }
 
-        // Try to add more items
-        assert_eq!(holder.try_push('e'), Err('e'));
-        assert_eq!(holder.try_push('f'), Err('f'));
-        assert!(holder.items.len() == 4);
-        assert!(holder.items.capacity() == 4);
-        // Make sure the addresses didn't change
-        assert_eq!(
-            holder.items.get(0).unwrap() as *const _ as usize,
-            first_item_address
-        );
-        assert_eq!(
-            holder.items.get(1).unwrap() as *const _ as usize,
-            second_item_address
-        );
+    /// Consume the interner, splitting the distinct items into the ones
+    /// which match the predicate and the ones which don't.
+    ///
+    /// Both parts keep the insertion order. Like [`into_iter`](IntoIterator::into_iter),
+    /// the items which aren't owned by the interner are skipped.
+    ///
+    /// ```
+    /// use libintern::Interner;
+    ///
+    /// let interner = Interner::new();
+    /// for i in [1, 2, 3, 2, 4] {
+    ///     interner.intern(i);
+    /// }
+    /// let (even, odd) = interner.into_partition(|i| i % 2 == 0);
+    /// assert_eq!(even, vec![2, 4]);
+    /// assert_eq!(odd, vec![1, 3]);
+    /// ```
+    pub fn into_partition<F: FnMut(&T) -> bool>(self, f: F) -> (Vec<T>, Vec<T>) {
+        self.into_iter().partition(f)
+    }
 
-        // Try to dereference the addresses, just to be sure
-        assert_eq!(
-            unsafe { *(first_item_address as *const char) },
-            'a');
-        assert_eq!(
-            unsafe { *(second_item_address as *const char) },
-            'b');
+    /// The number of items in the largest bucket of the hash index.
+    ///
+    /// All the items in a bucket have the same hash, and a lookup compares
+    /// the item with every item in the bucket, so a large value means
+    /// the hasher or the data cause many collisions.
+    /// It's zero if no items are interned.
+    pub fn max_bucket_len(&self) -> usize {
+        let state = self.state.borrow();
+        match &state.hashes {
+            Some(hashes) => hashes.iter().map(|&hash| state.bucket(hash).len()).max().unwrap_or(0),
+            None => state.index.values().map(Vec::len).max().unwrap_or(0)
+        }
     }
 
-    #[test]
-    fn interner_test() {
-        let mut int = Interner::new();
-        // Intern some things
-        let ref_a1 = int.intern('a');
-        let ref_b = int.intern('b');
-        let ref_a2 = int.intern('a');
-        // After this, only TWO items should be interned 'a' and 'b'. The second 'a' should have been discarded
-        assert_eq!(int.holders.len(), 1);
-        assert_eq!(int.holders[0].items.len(), 2);
-        // Now check that the addresses of ref_a1 and ref_a2 are equal
-        assert!(std::ptr::eq(ref_a1.as_ref(), ref_a2.as_ref()));
-        assert!(!std::ptr::eq(ref_a1.as_ref(), ref_b.as_ref()));
+    /// The total number of items all the holders have room for.
+    ///
+    /// Note that the spare room of a holder which isn't the last one is never used,
+    /// so `capacity() - len()` is only an upper bound of how many more items
+    /// can be interned before a new holder is allocated.
+    pub fn capacity(&self) -> usize {
+        self.state.borrow().holders.iter().map(|holder| holder.items.capacity()).sum()
+    }
 
-        let ref_b2 = int.intern('b');
+    /// Get a reference to the interned item with the given symbol.
+    ///
+    /// # Panics
+    /// Panics if the symbol was not produced by this interner.
+    pub fn resolve(&self, symbol: Symbol) -> Intern<'_, T> {
+        let reference = self.held_item(symbol);
+        // SAFETY: the symbol table only contains pointers to the held items
+        unsafe { self.transmute_held_item(reference) }
+    }
+
+    /// Get a reference to the interned item with the given symbol,
+    /// or `None` if the symbol is not valid for this interner.
+    ///
+    /// Unlike [`resolve`](Self::resolve), this never panics,
+    /// so it can be used for symbols coming from untrusted data.
+    pub fn try_resolve(&self, symbol: Symbol) -> Option<Intern<'_, T>> {
+        let reference = *self.state.borrow().symbols.get(symbol.0 as usize)?;
+        // SAFETY: the symbol table only contains pointers to the held items
+        Some(unsafe { self.transmute_held_item(reference) })
+    }
+
+    /// Returns true if the given symbol resolves to an item of this interner.
+    ///
+    /// As the symbols are dense, this is the same as checking that the symbol is below `len()`.
+    pub fn is_valid_symbol(&self, symbol: Symbol) -> bool {
+        (symbol.0 as usize) < self.len()
+    }
+
+    /// Turn a raw index, e.g. one read from a file, into a symbol of this interner.
+    ///
+    /// Returns `None` if no item has that index, so the returned symbol
+    /// can always be [`resolve`](Self::resolve)d.
+    pub fn symbol_from_index(&self, index: usize) -> Option<Symbol> {
+        if index < self.len() {
+            // The number of items always fits into a u32
+            Some(Symbol(index as u32))
+        } else {
+            None
+        }
+    }
+
+    /// Get references to the interned items with the given symbols, in the same order.
+    ///
+    /// Works like calling [`resolve`](Self::resolve) for every symbol,
+    /// but the symbol table is only borrowed once.
+    ///
+    /// # Panics
+    /// Panics if any of the symbols was not produced by this interner.
+    pub fn resolve_many(&self, symbols: &[Symbol]) -> Vec<Intern<'_, T>> {
+        let state = self.state.borrow();
+        symbols.iter()
+            .map(|symbol| match state.symbols.get(symbol.0 as usize) {
+                // SAFETY: the symbol table only contains pointers to the held items
+                Some(&reference) => unsafe { self.transmute_held_item(reference) },
+                None => panic!("symbol {} is out of range for an interner of {} items", symbol.0, state.symbols.len())
+            })
+            .collect()
+    }
+
+    /// Get a reference to the interned item at the given address.
+    ///
+    /// This is the inverse of [`Intern::addr`].
+    /// In debug builds, it panics if the address isn't the address of an item held by this interner.
+    ///
+    /// # Safety
+    /// The address must be the address of an item held by this interner,
+    /// i.e. produced by [`Intern::addr`] of a reference produced by this interner,
+    /// and the interner must not have been cleared since.
+    pub unsafe fn intern_from_addr(&self, addr: usize) -> Intern<'_, T> {
+        debug_assert!(self.state.borrow().holds_address(addr), "the address isn't held by the interner");
+        self.transmute_held_item(addr as *const T)
+    }
+
+    /// Remove all the interned items.
+    ///
+    /// All the holders are dropped, except for the first one, 
+    /// which keeps its original capacity.
+    /// Previously produced symbols are no longer valid.
+    ///
+    /// This requires a mutable reference, so no references to the items can be alive:
+    ///
+    /// ```compile_fail
+    /// use libintern::Interner;
+    ///
+    /// let mut interner = Interner::new();
+    /// let a = interner.intern('a');
+    /// interner.clear();
+    /// assert_eq!(*a, 'a');
+    /// ```
+    pub fn clear(&mut self) {
+        let state = self.state.get_mut();
+        state.symbols.clear();
+        state.external.clear();
+        state.clear_index();
+        state.holders.truncate(1);
+        state.holders[0].items.clear();
+        state.spare_holders.clear();
+    }
+
+    /// Remove all the interned items, but keep the memory of all the holders.
+    ///
+    /// Unlike [`clear`](Self::clear), which only keeps the first holder,
+    /// no holder is freed: the emptied holders are reused, in their original order,
+    /// before any new holder is allocated. So interning about as many items
+    /// as before the reset doesn't allocate any holders.
+    /// The kept holders only count towards [`capacity`](Self::capacity)
+    /// and [`holders_len`](Self::holders_len) once they are reused.
+    /// The kept holders which aren't larger than the last holder when they are due,
+    /// e.g. after [`reserve`](Self::reserve), are freed instead of being reused.
+    /// Use [`shrink_to_fit`](Self::shrink_to_fit) to free them.
+    ///
+    /// ```
+    /// use libintern::InternerBuilder;
+    ///
+    /// let mut interner = InternerBuilder::new().initial_capacity(1).build();
+    /// for i in 0..100 {
+    ///     interner.intern(i);
+    /// }
+    /// let holders = interner.holders_len();
+    /// interner.reset();
+    /// for i in 100..200 {
+    ///     interner.intern(i);
+    /// }
+    /// assert_eq!(interner.holders_len(), holders);
+    /// ```
+    pub fn reset(&mut self) {
+        let state = self.state.get_mut();
+        state.symbols.clear();
+        state.external.clear();
+        state.clear_index();
+        for holder in &mut state.holders {
+            holder.items.clear();
+        }
+        let mut spare_holders = state.holders.split_off(1);
+        // The spare holders are kept in the order in which they are reused
+        spare_holders.extend(state.spare_holders.drain(..).rev());
+        spare_holders.reverse();
+        state.spare_holders = spare_holders;
+    }
+
+    /// Record the current state of the interner, see [`truncate_to`](Self::truncate_to).
+    pub fn snapshot(&self) -> InternerSnapshot {
+        let state = self.state.borrow();
+        InternerSnapshot { items: state.symbols.len() }
+    }
+
+    /// Roll the interner back to the given snapshot.
+    ///
+    /// The items interned since the snapshot was taken are dropped,
+    /// as well as the holders which only held those items.
+    /// The symbols of the remaining items don't change,
+    /// the symbols of the dropped items are no longer valid.
+    ///
+    /// This requires a mutable reference, so no references to the items can be alive.
+    ///
+    /// ```
+    /// use libintern::Interner;
+    ///
+    /// let mut interner = Interner::new();
+    /// interner.intern('a');
+    /// let snapshot = interner.snapshot();
+    /// interner.intern('b');
+    /// interner.truncate_to(snapshot);
+    /// assert!(!interner.contains(&'b'));
+    /// assert_eq!(interner.len(), 1);
+    /// ```
+    pub fn truncate_to(&mut self, snapshot: InternerSnapshot) {
+        let state = self.state.get_mut();
+        let items = snapshot.items;
+        if items >= state.symbols.len() {
+            return
+        }
+        state.symbols.truncate(items);
+        state.external.retain(|(symbol, _)| (symbol.0 as usize) < items);
+        match &mut state.hashes {
+            Some(hashes) => hashes.truncate(items),
+            None => state.index.retain(|_, bucket| {
+                bucket.retain(|symbol| (symbol.0 as usize) < items);
+                !bucket.is_empty()
+            })
+        }
+        // The remaining items are the first ones, which are held by the first holders.
+        // The holders to keep are found from what they hold, as the snapshot
+        // may be older than a clear or come from another interner
+        let mut kept_items = items - state.external.len();
+        let mut kept_holders = 0;
+        for holder in &mut state.holders {
+            kept_holders += 1;
+            if holder.items.len() >= kept_items {
+                holder.items.truncate(kept_items);
+                break
+            }
+            kept_items -= holder.items.len();
+        }
+        state.holders.truncate(kept_holders);
+    }
+
+    /// Make sure at least `additional` more distinct items can be interned
+    /// without allocating a new holder.
+    ///
+    /// If the last holder doesn't have enough spare room already,
+    /// a single new holder large enough for all of the items is added.
+    /// The spare room of the previously last holder isn't used after that.
+    pub fn reserve(&self, additional: usize) {
+        let mut state = self.state.borrow_mut();
+        let last_holder = &state.holders.last().unwrap().items;
+        if last_holder.capacity() - last_holder.len() >= additional {
+            return
+        }
+        let capacity = state.next_holder_capacity().max(additional);
+        state.push_holder(InternedItemHolder::new(capacity));
+    }
+
+    /// Make sure at least `additional` more distinct items can be interned
+    /// without allocating a new holder, allocating no more than needed.
+    ///
+    /// Works like [`reserve`](Self::reserve), but the new holder, if any,
+    /// has room for exactly `additional` items instead of following the growth of the holders.
+    /// The holder after it grows from its capacity as usual.
+    pub fn reserve_exact(&self, additional: usize) {
+        let mut state = self.state.borrow_mut();
+        let last_holder = &state.holders.last().unwrap().items;
+        if last_holder.capacity() - last_holder.len() >= additional {
+            return
+        }
+        state.push_holder(InternedItemHolder::new_exact(additional));
+    }
+
+    /// Set how many items are expected to be interned in total.
+    ///
+    /// When a new holder is needed, it's made large enough for all the items
+    /// which are still expected, instead of just following the growth of the holders.
+    /// This keeps the number of holders low when the number of items is roughly known,
+    /// without allocating the memory upfront like [`reserve`](Self::reserve) does.
+    /// Zero, the default, means no items are expected.
+    pub fn set_expected_items(&self, expected_items: usize) {
+        self.state.borrow_mut().expected_items = expected_items;
+    }
+
+    /// Set a callback which is called whenever a new holder is allocated,
+    /// with the capacity of the new holder.
+    ///
+    /// This makes it possible to trace the growth of the interner.
+    /// The callback replaces the previously set one, if any,
+    /// and it isn't carried over to a [`clone`](Clone::clone) of the interner.
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use libintern::InternerBuilder;
+    ///
+    /// let mut interner = InternerBuilder::new().initial_capacity(1).build();
+    /// let capacities = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = Arc::clone(&capacities);
+    /// interner.on_grow(move |capacity| sink.lock().unwrap().push(capacity));
+    /// interner.intern('a');
+    /// interner.intern('b');
+    /// assert_eq!(capacities.lock().unwrap().len(), 1);
+    /// ```
+    pub fn on_grow<F: FnMut(usize) + Send + 'static>(&mut self, f: F) {
+        self.state.get_mut().on_grow = Some(Box::new(f));
+    }
+
+    /// The number of holders of the items.
+    pub fn holders_len(&self) -> usize {
+        self.state.borrow().holders.len()
+    }
+
+    /// Release the memory which isn't used by the interned items.
+    ///
+    /// Trailing holders which hold no items (e.g. ones added by [`reserve`](Self::reserve))
+    /// are dropped, the first holder is always kept.
+    ///
+    /// The holders which hold some items are never shrunk: shrinking a holder
+    /// could move its items, and many parts of the interner rely on the addresses
+    /// of the items being stable.
+    pub fn shrink_to_fit(&mut self) {
+        let state = self.state.get_mut();
+        while state.holders.len() > 1 && state.holders.last().unwrap().items.is_empty() {
+            state.holders.pop();
+        }
+        state.spare_holders = Vec::new();
+        state.symbols.shrink_to_fit();
+        state.index.shrink_to_fit();
+        if let Some(hashes) = &mut state.hashes {
+            hashes.shrink_to_fit();
+        }
+    }
+
+    /// Move all the items held by this interner into a single holder,
+    /// which has room for exactly the interned items.
+    /// The holders kept by [`reset`](Self::reset) are freed as well.
+    ///
+    /// This keeps the items contiguous in memory, e.g. before a read-heavy phase.
+    /// The symbols don't change, but **the addresses of the items do**,
+    /// so any addresses obtained before (e.g. by [`Intern::addr`]) are no longer valid.
+    /// The references to the items can't be alive, as this requires a mutable reference.
+    /// The items interned by [`intern_static`](Self::intern_static)
+    /// and [`intern_arc`](Self::intern_arc) aren't held by the holders, so they don't move.
+    ///
+    /// ```
+    /// use libintern::InternerBuilder;
+    ///
+    /// let mut interner = InternerBuilder::new().initial_capacity(1).build();
+    /// let symbols: Vec<_> = (0..10).map(|i| interner.intern_symbol(i)).collect();
+    /// interner.compact();
+    /// assert_eq!(interner.holders_len(), 1);
+    /// assert_eq!(*interner.resolve(symbols[7]), 7);
+    /// ```
+    pub fn compact(&mut self) {
+        let state = self.state.get_mut();
+        state.spare_holders = Vec::new();
+        let held = state.symbols.len() - state.external.len();
+        if state.holders.len() == 1 && state.holders[0].items.capacity() == held.max(1) {
+            return
+        }
+        let mut compacted = InternedItemHolder::new(held.max(1));
+        for holder in state.holders.drain(..) {
+            // The new holder has room for all the items, so it's never reallocated
+            compacted.items.extend(holder.items);
+        }
+        compacted.debug_assert_not_reallocated();
+        let mut held_items = compacted.items.iter();
+        let mut external = state.external.iter().peekable();
+        for (id, reference) in state.symbols.iter_mut().enumerate() {
+            match external.peek() {
+                // External items don't move
+                Some((symbol, _)) if symbol.0 as usize == id => {
+                    external.next();
+                },
+                _ => *reference = held_items.next().unwrap()
+            }
+        }
+        state.push_holder(compacted);
+    }
+
+    /// The number of times interning an item found an equal item already interned.
+    ///
+    /// Together with [`misses`](Self::misses), this measures how much
+    /// the deduplication saves. Lookups which don't intern anything,
+    /// like [`get_interned`](Self::get_interned), aren't counted.
+    /// The count isn't reset by [`clear`](Self::clear).
+    pub fn hits(&self) -> u64 {
+        self.state.borrow().hits
+    }
+
+    /// The number of times interning an item interned a new item.
+    ///
+    /// The count isn't reset by [`clear`](Self::clear).
+    pub fn misses(&self) -> u64 {
+        self.state.borrow().misses
+    }
+
+    /// Get statistics about the storage of this interner.
+    pub fn stats(&self) -> InternerStats {
+        let state = self.state.borrow();
+        InternerStats {
+            holders: state.holders.len(),
+            capacity: self.capacity(),
+            items: state.symbols.len(),
+            item_bytes: core::mem::size_of::<T>() * state.symbols.len()
+        }
+    }
+
+    /// Get the pointer to the held item with the given symbol.
+    ///
+    /// # Panics
+    /// Panics if the symbol was not produced by this interner.
+    fn held_item(&self, symbol: Symbol) -> *const T {
+        let state = self.state.borrow();
+        match state.symbols.get(symbol.0 as usize) {
+            Some(&reference) => reference,
+            None => panic!("symbol {} is out of range for an interner of {} items", symbol.0, state.symbols.len())
+        }
+    }
+
+    /// Transmute a pointer to an item held by this interner
+    /// into the Intern<T> type.
+    #[inline]
+    unsafe fn transmute_held_item(&self, item: *const T) -> Intern<'_, T> {
+        // SAFETY: The returned reference borrows the interner, so we guarantee the interner
+        // is alive and not mutably borrowed as long as the reference is alive.
+        // Furthermore, through a shared reference, the held data is NEVER
+        // mutated nor moved AND only immutable references to the data exist.
+        // Therefore we uphold all guarantees and can assume safety when dereferencing
+        let reference: &T = &*item;
+        // SAFETY: I believe for the reasons stated above, this is also safe
+        let pinned_reference: Pin<&T> = Pin::new_unchecked(reference);
+        Intern(pinned_reference)
+    }
+
+    /// Iterate over all interned items, in insertion order.
+    ///
+    /// The yielded references are identical to the ones [`intern`](Self::intern) returns.
+    /// Items interned after this method is called are not yielded.
+    pub fn iter(&self) -> Iter<'_, 'a, T, S, P> {
+        Iter { interner: self, next_id: 0, end: self.len() }
+    }
+
+    /// Iterate over all interned items together with their symbols, in insertion order.
+    ///
+    /// The yielded symbols resolve to the yielded items.
+    /// Items interned after this method is called are not yielded.
+    pub fn iter_symbols(&self) -> impl Iterator<Item = (Symbol, Intern<'_, T>)> {
+        self.symbols().zip(self.iter())
+    }
+
+    /// Build side data for every interned item, indexed by its symbol.
+    ///
+    /// The returned vector has exactly one value per interned item,
+    /// the value at position `symbol.0` belongs to the item with that symbol.
+    /// Items interned by `f` itself don't get a value.
+    ///
+    /// ```
+    /// use libintern::Interner;
+    ///
+    /// let interner = Interner::new();
+    /// let hello = interner.intern_symbol("hello");
+    /// interner.intern_symbol("world");
+    /// let lengths = interner.build_map(|_, word| word.len());
+    /// assert_eq!(lengths, vec![5, 5]);
+    /// assert_eq!(lengths[hello.0 as usize], 5);
+    /// ```
+    pub fn build_map<V, F: FnMut(Symbol, Intern<'_, T>) -> V>(&self, mut f: F) -> Vec<V> {
+        self.iter_symbols().map(|(symbol, item)| f(symbol, item)).collect()
+    }
+
+    /// Get the earliest interned item, if any.
+    pub fn first(&self) -> Option<Intern<'_, T>> {
+        let item = *self.state.borrow().symbols.first()?;
+        // SAFETY: the symbol table only contains pointers to the held items
+        Some(unsafe { self.transmute_held_item(item) })
+    }
+
+    /// Get the most recently interned distinct item, if any.
+    ///
+    /// Interning an item which is already interned doesn't make it the last one.
+    pub fn last(&self) -> Option<Intern<'_, T>> {
+        let item = *self.state.borrow().symbols.last()?;
+        // SAFETY: the symbol table only contains pointers to the held items
+        Some(unsafe { self.transmute_held_item(item) })
+    }
+}
+
+impl<T: 'static + Eq, S: 'static, P: 'static + InternerPolicy> Interner<'static, T, S, P> {
+    /// Leak the interner, so that it lives for the rest of the program.
+    ///
+    /// The references produced by the leaked interner are `'static`,
+    /// which is useful e.g. for global configuration.
+    /// The memory of the interner is never freed.
+    ///
+    /// ```
+    /// use libintern::{Intern, Interner};
+    ///
+    /// let interner = Interner::new().leak();
+    /// let a: Intern<'static, char> = interner.intern('a');
+    /// assert_eq!(*a, 'a');
+    /// ```
+    pub fn leak(self) -> &'static Self {
+        Box::leak(Box::new(self))
+    }
+}
+
+impl<'a, T: 'a + Eq, S: Default, P: InternerPolicy> Default for Interner<'a, T, S, P> {
+    fn default() -> Self {
+        Self::with_policy_and_hasher(S::default())
+    }
+}
+
+/// Create a deep copy of the interner.
+///
+/// The clone holds its own copies of all the items, with the same symbols
+/// as in the original interner, only the static and the shared items are shared. References produced by the original interner
+/// and by the clone are never equal, even if the items they point to are,
+/// because references to interned items are compared by their addresses:
+///
+/// ```
+/// use libintern::Interner;
+///
+/// let interner = Interner::new();
+/// let a = interner.intern('a');
+/// let clone = interner.clone();
+/// let other_a = clone.intern('a');
+/// assert_eq!(*a, *other_a);
+/// assert_ne!(a, other_a);
+/// ```
+impl<'a, T: 'a + Eq + Clone, S: Clone, P: InternerPolicy> Clone for Interner<'a, T, S, P> {
+    fn clone(&self) -> Self {
+        let state = self.state.borrow();
+        // Items are always added to the last holder,
+        // so the holders hold the items in insertion order
+        let holders: Vec<InternedItemHolder<T>> = state.holders.iter()
+            .map(|holder| {
+                let mut new_holder = InternedItemHolder::new(holder.items.capacity());
+                new_holder.exact = holder.exact;
+                new_holder.items.extend(holder.items.iter().cloned());
+                new_holder
+            })
+            .collect();
+        let external = state.external.clone();
+        let mut held_items = holders.iter().flat_map(|holder| holder.items.iter());
+        let mut external_items = external.iter().peekable();
+        let symbols = (0..state.symbols.len())
+            .map(|id| match external_items.peek() {
+                Some((symbol, _)) if symbol.0 as usize == id => match &external_items.next().unwrap().1 {
+                    ExternalItem::Boxed(item) => &**item as *const T,
+                    // Static and shared items are shared, not copied
+                    _ => state.symbols[id]
+                },
+                _ => held_items.next().unwrap() as *const T
+            })
+            .collect();
+        Self {
+            state: RefCell::new(InternerState {
+                holders,
+                spare_holders: Vec::new(),
+                symbols,
+                external,
+                index: state.index.clone(),
+                hashes: state.hashes.clone(),
+                linear_threshold: state.linear_threshold,
+                boxed: state.boxed,
+                growth_factor: state.growth_factor,
+                expected_items: state.expected_items,
+                hits: state.hits,
+                misses: state.misses,
+                // The callback can't be cloned
+                on_grow: None,
+                _policy: PhantomData
+            }),
+            hash_builder: self.hash_builder.clone(),
+            _ph: PhantomData
+        }
+    }
+}
+
+impl<'a, T: 'a + Eq + Hash, S: BuildHasher, P: InternerPolicy> Interner<'a, T, S, P> {
+    /// Intern an item.
+    ///
+    /// This consumes the item by adding it to the intern-list and returns a reference to it.
+    /// The reference borrows the interner, which makes sure the item lives long enough.
+    ///
+    /// This item is dropped if an item equal to this one is already interned,
+    /// in which case a reference to the already interned item is returned instead.
+    pub fn intern(&self, item: T) -> Intern<'_, T> {
+        let symbol = self.intern_symbol(item);
+        self.resolve(symbol)
+    }
+
+    /// Intern an item and return a plain reference to it.
+    ///
+    /// Works like [`intern`](Self::intern) followed by [`Intern::into_ref`],
+    /// for when the interner is only used to deduplicate the items
+    /// and the items are compared by value.
+    pub fn intern_ref_only(&self, item: T) -> &T {
+        self.intern(item).into_ref()
+    }
+
+    /// Intern an item and return its [`Symbol`].
+    ///
+    /// Works exactly like [`intern`](Self::intern), except that the stable id
+    /// of the item is returned instead of a reference to it.
+    /// Use [`resolve`](Self::resolve) to get the reference back.
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` distinct items would be interned.
+    pub fn intern_symbol(&self, item: T) -> Symbol {
+        self.get_or_intern_symbol(item).0
+    }
+
+    /// Intern an item and return both a reference to it and its [`Symbol`].
+    ///
+    /// Works exactly like [`intern`](Self::intern) and [`intern_symbol`](Self::intern_symbol)
+    /// combined, with a single lookup.
+    pub fn intern_full(&self, item: T) -> (Intern<'_, T>, Symbol) {
+        let symbol = self.intern_symbol(item);
+        (self.resolve(symbol), symbol)
+    }
+
+    /// Intern an item and tell whether it was newly inserted.
+    ///
+    /// Works exactly like [`intern`](Self::intern), but also returns `true`
+    /// if no equal item was interned before, i.e. the given item is now held by the interner.
+    pub fn get_or_intern(&self, item: T) -> (Intern<'_, T>, bool) {
+        let (symbol, inserted) = self.get_or_intern_symbol(item);
+        (self.resolve(symbol), inserted)
+    }
+
+    /// Intern an item and tell whether it was newly inserted, in the shape of an entry.
+    ///
+    /// Works exactly like [`get_or_intern`](Self::get_or_intern).
+    ///
+    /// ```
+    /// use libintern::{InternEntry, Interner};
+    ///
+    /// let interner = Interner::new();
+    /// interner.intern('a');
+    /// match interner.intern_entry('a') {
+    ///     InternEntry::Occupied(a) => assert_eq!(*a, 'a'),
+    ///     InternEntry::Vacant(_) => unreachable!()
+    /// }
+    /// ```
+    pub fn intern_entry(&self, item: T) -> InternEntry<'_, T> {
+        match self.get_or_intern(item) {
+            (interned, true) => InternEntry::Vacant(interned),
+            (interned, false) => InternEntry::Occupied(interned)
+        }
+    }
+
+    /// Intern a static item without copying it.
+    ///
+    /// If no equal item is interned yet, the interner refers to the static item directly
+    /// instead of holding a copy of it, which saves memory for large fixed vocabularies.
+    /// Otherwise, a reference to the already interned item is returned.
+    ///
+    /// Static items are deduplicated with the other items as usual,
+    /// but they are not yielded when the interner is consumed by [`into_iter`](IntoIterator::into_iter).
+    ///
+    /// The interner can be sent to another thread while the item is used on the current one,
+    /// so the item must be `Sync`.
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` distinct items would be interned.
+    pub fn intern_static(&self, item: &'static T) -> Intern<'_, T>
+    where
+        T: Sync
+    {
+        // SAFETY: the item is static
+        let symbol = unsafe { self.intern_static_symbol(item) };
+        self.resolve(symbol)
+    }
+
+    /// Intern a static item without copying it and return its symbol.
+    ///
+    /// # Safety
+    /// The item must live for the rest of the program.
+    unsafe fn intern_static_symbol(&self, item: *const T) -> Symbol {
+        let hash = self.hash_builder.hash_one(&*item);
+        let mut state = self.state.borrow_mut();
+        match state.find_interned(hash, &*item) {
+            Some(symbol) => symbol,
+            None => state.hold_static_symbol(hash, item)
+        }
+    }
+
+    /// Intern a shared item without copying it.
+    ///
+    /// If no equal item is interned yet, the interner keeps the shared item alive
+    /// and refers to it directly, instead of holding a copy of it.
+    /// Otherwise, the given reference is dropped and a reference to the already interned item is returned.
+    ///
+    /// Shared items are deduplicated with the other items as usual,
+    /// but they are not yielded when the interner is consumed by [`into_iter`](IntoIterator::into_iter).
+    ///
+    /// The interner can be sent to another thread while the other references to the item
+    /// are used on the current one, so the item must be `Sync`.
+    /// An `Rc` can't be interned for the same reason, use an [`RcInterner`] for that.
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` distinct items would be interned.
+    pub fn intern_arc(&self, item: Arc<T>) -> Intern<'_, T>
+    where
+        T: Sync
+    {
+        let symbol = self.intern_shared_symbol(item);
+        self.resolve(symbol)
+    }
+
+    fn intern_shared_symbol(&self, item: Arc<T>) -> Symbol {
+        let hash = self.hash_builder.hash_one(&*item);
+        let mut state = self.state.borrow_mut();
+        match state.find_interned(hash, &*item) {
+            Some(symbol) => symbol,
+            None => state.hold_shared_symbol(hash, item)
+        }
+    }
+
+    /// Intern a boxed item without moving it out of the box.
+    ///
+    /// If no equal item is interned yet, the interner keeps the box itself
+    /// and refers to the item inside it, so a large item isn't copied into the holders.
+    /// Otherwise, the box is dropped and a reference to the already interned item is returned.
+    ///
+    /// Unlike the static and shared items, boxed items are owned by the interner,
+    /// so they are yielded when it's consumed by [`into_iter`](IntoIterator::into_iter).
+    ///
+    /// ```
+    /// use libintern::Interner;
+    ///
+    /// let interner = Interner::new();
+    /// let big = interner.intern_boxed(Box::new([0u8; 1024]));
+    /// assert_eq!(interner.intern([0u8; 1024]), big);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` distinct items would be interned.
+    pub fn intern_boxed(&self, item: Box<T>) -> Intern<'_, T> {
+        let symbol = self.intern_boxed_symbol(item);
+        self.resolve(symbol)
+    }
+
+    fn intern_boxed_symbol(&self, item: Box<T>) -> Symbol {
+        let hash = self.hash_builder.hash_one(&*item);
+        let mut state = self.state.borrow_mut();
+        match state.find_interned(hash, &*item) {
+            Some(symbol) => symbol,
+            None => state.hold_boxed_symbol(hash, item)
+        }
+    }
+
+    /// Intern all the items and return references to them, in order.
+    ///
+    /// Equal items are collapsed into references to the same interned item.
+    pub fn intern_iter<I: IntoIterator<Item = T>>(&self, items: I) -> Vec<Intern<'_, T>> {
+        let items = items.into_iter();
+        let mut interned = Vec::with_capacity(items.size_hint().0);
+        interned.extend(items.map(|item| self.intern(item)));
+        interned
+    }
+
+    /// Intern all the items and tell which of them were newly inserted, in order.
+    ///
+    /// Works like [`get_or_intern`](Self::get_or_intern) applied to every item,
+    /// so an item is `true` only if no equal item was interned before it,
+    /// neither before this call nor earlier in the batch.
+    ///
+    /// ```
+    /// use libintern::Interner;
+    ///
+    /// let interner = Interner::new();
+    /// interner.intern("a");
+    /// let report = interner.intern_reporting(vec!["a", "b", "b"]);
+    /// let fresh: Vec<bool> = report.iter().map(|&(_, fresh)| fresh).collect();
+    /// assert_eq!(fresh, vec![false, true, false]);
+    /// ```
+    pub fn intern_reporting<I: IntoIterator<Item = T>>(&self, items: I) -> Vec<(Intern<'_, T>, bool)> {
+        let items = items.into_iter();
+        let mut interned = Vec::with_capacity(items.size_hint().0);
+        interned.extend(items.map(|item| self.get_or_intern(item)));
+        interned
+    }
+
+    /// Move all the items of another interner into this one.
+    ///
+    /// The items are interned in their original order and deduplicated against
+    /// the items of this interner. Returns the new symbols of the items,
+    /// the new symbol of the item with symbol `Symbol(i)` in the other interner being at index `i`,
+    /// so that symbols stored elsewhere can be rewritten.
+    ///
+    /// ```
+    /// use libintern::{Interner, Symbol};
+    ///
+    /// let interner = Interner::new();
+    /// interner.intern('a');
+    /// let other = Interner::new();
+    /// other.intern('b');
+    /// other.intern('a');
+    /// assert_eq!(interner.merge(other), vec![Symbol(1), Symbol(0)]);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` distinct items would be interned.
+    pub fn merge<'b, S2>(&self, other: Interner<'b, T, S2>) -> Vec<Symbol> {
+        let InternerState { holders, symbols, external, .. } = other.state.into_inner();
+        let mut held_items = holders.into_iter().flat_map(|holder| holder.items);
+        let mut external = external.into_iter().peekable();
+        (0..symbols.len())
+            .map(|id| match external.peek() {
+                Some((symbol, _)) if symbol.0 as usize == id => match external.next().unwrap().1 {
+                    // SAFETY: the item was interned from a static reference
+                    ExternalItem::Static => unsafe { self.intern_static_symbol(symbols[id]) },
+                    ExternalItem::Shared(item) => self.intern_shared_symbol(item),
+                    ExternalItem::Boxed(item) => self.intern_boxed_symbol(item)
+                },
+                _ => self.intern_symbol(held_items.next().unwrap())
+            })
+            .collect()
+    }
+
+    /// Create a new interner holding copies of the items matching the predicate.
+    ///
+    /// The items are interned in their original order, so their symbols are dense again.
+    /// Like with [`clone`](Clone::clone), the references produced by the new interner
+    /// are never equal to the ones produced by this one, and the new interner is configured
+    /// like this one: its first holder is as large as the first holder of this one,
+    /// and it grows, boxes the items and builds its index the same way.
+    pub fn filter_to_new<F: Fn(&T) -> bool>(&self, predicate: F) -> Self
+    where
+        T: Clone,
+        S: Clone
+    {
+        let filtered = {
+            let state = self.state.borrow();
+            let mut filtered = Self::with_config(state.holders[0].items.capacity(), state.growth_factor, self.hash_builder.clone());
+            let filtered_state = filtered.state.get_mut();
+            filtered_state.expected_items = state.expected_items;
+            filtered_state.set_linear_threshold(state.linear_threshold);
+            filtered_state.boxed = state.boxed;
+            filtered
+        };
+        for item in self.iter().filter(|item| predicate(item)) {
+            filtered.intern(T::clone(&item));
+        }
+        filtered
+    }
+
+    /// Verify the internal invariants of the interner.
+    ///
+    /// This is meant to be called from tests and fuzzing harnesses,
+    /// it takes time proportional to the number of interned items.
+    /// Returns a description of the first violated invariant, if any.
+    pub fn check_invariants(&self) -> Result<(), String> {
+        let state = self.state.borrow();
+        for (i, holder) in state.holders.iter().enumerate() {
+            if holder.items.len() > holder.items.capacity() {
+                return Err(format!("holder {} holds more items than its capacity", i))
+            }
+            if holder.items.capacity() == 0 {
+                return Err(format!("holder {} has no capacity", i))
+            }
+        }
+        // The holders added by `reserve_exact` don't follow the growth,
+        // but the holders after them grow from their capacity
+        for (i, pair) in state.holders.windows(2).enumerate() {
+            if !pair[1].exact && pair[0].items.capacity() >= pair[1].items.capacity() {
+                return Err(format!("the capacity of holder {} is not greater than the capacity of holder {}", i + 1, i))
+            }
+        }
+        let held = state.holders.iter().map(|holder| holder.items.len()).sum::<usize>() + state.external.len();
+        if held != state.symbols.len() {
+            return Err(format!("{} items are held, but {} symbols are assigned", held, state.symbols.len()))
+        }
+        let indexed: usize = match &state.hashes {
+            Some(hashes) => hashes.len(),
+            None => state.index.values().map(Vec::len).sum()
+        };
+        if indexed != state.symbols.len() {
+            return Err(format!("{} symbols are indexed, but {} symbols are assigned", indexed, state.symbols.len()))
+        }
+        for (id, &reference) in state.symbols.iter().enumerate() {
+            // SAFETY: the symbol table only contains pointers to the held items
+            let hash = self.hash_builder.hash_one(unsafe { &*reference });
+            let bucket = state.bucket(hash);
+            if !bucket.contains(&Symbol(id as u32)) {
+                return Err(format!("symbol {} is not indexed by the hash of its item", id))
+            }
+            // Equal items have equal hashes, so it's enough to look for duplicates in the bucket
+            for &other in bucket.iter().filter(|other| other.0 as usize > id) {
+                // SAFETY: as above
+                if unsafe { *reference == *state.symbols[other.0 as usize] } {
+                    return Err(format!("the items with symbols {} and {} are equal", id, other.0))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get_or_intern_symbol(&self, item: T) -> (Symbol, bool) {
+        self.get_or_intern_hashed_symbol(self.hash_builder.hash_one(&item), item)
+    }
+
+    /// Intern an item whose hash by the hasher of this interner is `hash`.
+    fn get_or_intern_hashed_symbol(&self, hash: u64, item: T) -> (Symbol, bool) {
+        let mut state = self.state.borrow_mut();
+        // Look whether an item equal to this one already exists
+        match state.find_interned(hash, &item) {
+            Some(symbol) => (symbol, false),
+            // The new item is unique, add it to the holder
+            None => (state.hold_new_symbol(hash, item), true)
+        }
+    }
+
+    /// Intern an item by a borrowed form of it.
+    ///
+    /// If an equal item is already interned, a reference to it is returned
+    /// without constructing anything. Otherwise the owned item is created
+    /// from the key via [`From`] and interned.
+    pub fn intern_ref<Q>(&self, key: &Q) -> Intern<'_, T>
+    where
+        T: Borrow<Q> + for<'q> From<&'q Q>,
+        Q: ?Sized + Eq + Hash
+    {
+        let hash = self.hash_builder.hash_one(key);
+        let mut state = self.state.borrow_mut();
+        let symbol = match state.find_interned(hash, key) {
+            Some(symbol) => symbol,
+            // The hash of the owned item is the same as the hash of the key, see [`Borrow`]
+            None => state.hold_new_symbol(hash, T::from(key))
+        };
+        drop(state);
+        self.resolve(symbol)
+    }
+
+    /// Intern an item which is either borrowed or owned.
+    ///
+    /// The item is looked up by its borrowed form first, so if an equal item
+    /// is already interned, a borrowed item is never turned into an owned one.
+    /// Otherwise the item is turned into an owned one via [`Cow::into_owned`] and interned.
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use libintern::Interner;
+    ///
+    /// let interner: Interner<String> = Interner::new();
+    /// let a = interner.intern_cow(Cow::<str>::Owned(String::from("a")));
+    /// assert_eq!(interner.intern_cow(Cow::Borrowed("a")), a);
+    /// ```
+    pub fn intern_cow<Q>(&self, item: Cow<'_, Q>) -> Intern<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + ToOwned + Eq + Hash,
+        Q::Owned: Into<T>
+    {
+        let hash = self.hash_builder.hash_one(&*item);
+        let mut state = self.state.borrow_mut();
+        let symbol = match state.find_interned(hash, &*item) {
+            Some(symbol) => symbol,
+            // The hash of the owned item is the same as the hash of the borrowed one, see [`Borrow`]
+            None => state.hold_new_symbol(hash, item.into_owned().into())
+        };
+        drop(state);
+        self.resolve(symbol)
+    }
+
+    /// Intern an item given by its borrowed form, building the owned item only if needed.
+    ///
+    /// If an item equal to `key` is already interned, it's returned and `make` is never called.
+    /// Otherwise `make` is called to build the owned item, which must borrow as `key`,
+    /// and the built item is interned.
+    ///
+    /// `make` is called while the interner is not borrowed, so it may intern other items.
+    ///
+    /// ```
+    /// use libintern::Interner;
+    ///
+    /// let interner: Interner<String> = Interner::new();
+    /// let a = interner.get_or_intern_with("a", || String::from("a"));
+    /// assert_eq!(interner.get_or_intern_with("a", || unreachable!()), a);
+    /// ```
+    pub fn get_or_intern_with<Q, F>(&self, key: &Q, make: F) -> Intern<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+        F: FnOnce() -> T
+    {
+        let hash = self.hash_builder.hash_one(key);
+        let found = self.state.borrow_mut().find_interned(hash, key);
+        let symbol = match found {
+            Some(symbol) => symbol,
+            None => {
+                let item = make();
+                debug_assert!(item.borrow() == key, "the built item doesn't borrow as the key");
+                let mut state = self.state.borrow_mut();
+                // `make` might have interned an equal item itself
+                match state.find_interned(hash, key) {
+                    Some(symbol) => symbol,
+                    // The hash of the owned item is the same as the hash of the key, see [`Borrow`]
+                    None => state.hold_new_symbol(hash, item)
+                }
+            }
+        };
+        self.resolve(symbol)
+    }
+
+    /// Intern an item given by anything which converts into a [`Cow`].
+    ///
+    /// Works like [`intern_cow`](Self::intern_cow), so either an owned item
+    /// or a borrowed one can be given, and a borrowed item is only turned into an owned one
+    /// if no equal item is interned yet.
+    ///
+    /// ```
+    /// use libintern::Interner;
+    ///
+    /// let interner: Interner<String> = Interner::new();
+    /// let a = interner.intern_any("a");
+    /// assert_eq!(interner.intern_any(String::from("a")), a);
+    /// assert_eq!(interner.intern_any(&String::from("a")), a);
+    /// ```
+    pub fn intern_any<'v, Q, V>(&self, item: V) -> Intern<'_, T>
+    where
+        V: Into<Cow<'v, Q>>,
+        T: Borrow<Q>,
+        Q: 'v + ?Sized + ToOwned + Eq + Hash,
+        Q::Owned: Into<T>
+    {
+        self.intern_cow(item.into())
+    }
+
+    /// Returns true if an item equal to the given one is interned.
+    ///
+    /// The item can be given by any borrowed form of it.
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash
+    {
+        let hash = self.hash_builder.hash_one(item);
+        self.state.borrow().find_held(hash, item).is_some()
+    }
+
+    /// Get a reference to an already interned item, without interning it.
+    ///
+    /// Returns `None` if no item equal to this one is interned.
+    /// The returned reference is identical to the one [`intern`](Self::intern) would return.
+    pub fn get_interned<Q>(&self, item: &Q) -> Option<Intern<'_, T>>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash
+    {
+        let hash = self.hash_builder.hash_one(item);
+        let symbol = self.state.borrow().find_held(hash, item)?;
+        Some(self.resolve(symbol))
+    }
+
+    /// Get references to the already interned items equal to the given ones, without interning them.
+    ///
+    /// Works like calling [`get_interned`](Self::get_interned) for every query, in order,
+    /// but the index is only borrowed once. The missing items are `None`.
+    ///
+    /// ```
+    /// use libintern::Interner;
+    ///
+    /// let interner: Interner<String> = Interner::new();
+    /// let a = interner.intern(String::from("a"));
+    /// assert_eq!(interner.get_all(["a", "b", "a"]), vec![Some(a), None, Some(a)]);
+    /// ```
+    pub fn get_all<'q, Q, I>(&self, queries: I) -> Vec<Option<Intern<'_, T>>>
+    where
+        I: IntoIterator<Item = &'q Q>,
+        T: Borrow<Q>,
+        Q: 'q + ?Sized + Eq + Hash
+    {
+        let state = self.state.borrow();
+        queries.into_iter()
+            .map(|item| {
+                let symbol = state.find_held(self.hash_builder.hash_one(item), item)?;
+                // SAFETY: the symbol table only contains pointers to the held items
+                Some(unsafe { self.transmute_held_item(state.symbols[symbol.0 as usize]) })
+            })
+            .collect()
+    }
+
+    /// Try to get a reference to an interned item equal to the given one.
+    ///
+    /// This never allocates nor interns anything, it's the same as
+    /// [`get_interned`](Self::get_interned), so it can be called
+    /// while other references to the interned items are alive.
+    pub fn try_get<Q>(&self, item: &Q) -> Option<Intern<'_, T>>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash
+    {
+        self.get_interned(item)
+    }
+}
+
+/// Get the interned item with the given symbol, like [`Interner::resolve`].
+///
+/// # Panics
+/// Panics if the symbol was not produced by this interner.
+impl<'a, T: 'a + Eq, S, P: InternerPolicy> Index<Symbol> for Interner<'a, T, S, P> {
+    type Output = T;
+
+    fn index(&self, symbol: Symbol) -> &T {
+        // SAFETY: the symbol table only contains pointers to the held items,
+        // which are never mutated nor moved while the interner is borrowed
+        unsafe { &*self.held_item(symbol) }
+    }
+}
+
+impl<'a, T: 'a + Eq + Hash, S: BuildHasher + Default, P: InternerPolicy> core::iter::FromIterator<T> for Interner<'a, T, S, P> {
+    /// Create an interner and intern all the items, in order.
+    /// Duplicate items are dropped.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut interner = Self::default();
+        interner.extend(iter);
+        interner
+    }
+}
+
+impl<'a, T: 'a + Eq + Hash, S: BuildHasher, P: InternerPolicy> Extend<T> for Interner<'a, T, S, P> {
+    /// Intern all the items, in order.
+    /// Duplicate items are dropped.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.intern(item);
+        }
+    }
+}
+
+impl<'a, 'b, T: 'a + Eq + Hash + Clone, S: BuildHasher, P: InternerPolicy> Extend<Intern<'b, T>> for Interner<'a, T, S, P> {
+    /// Intern copies of the referenced items, in order.
+    ///
+    /// The references usually come from a different interner.
+    /// They aren't reused: the items are cloned into this interner
+    /// (only if no equal item is interned yet) and new references are produced for them.
+    fn extend<I: IntoIterator<Item = Intern<'b, T>>>(&mut self, iter: I) {
+        for item in iter {
+            self.get_or_intern_with(&*item, || (*item).clone());
+        }
+    }
+}
+
+impl<'a, T: 'a + Eq, S, P> IntoIterator for Interner<'a, T, S, P> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Consume the interner, yielding the distinct items in insertion order.
+    ///
+    /// The items interned by [`intern_static`](Interner::intern_static)
+    /// and [`intern_arc`](Interner::intern_arc) aren't owned by the interner, so they are skipped.
+    fn into_iter(self) -> Self::IntoIter {
+        let state = self.state.into_inner();
+        IntoIter {
+            holders: state.holders.into_iter(),
+            current: Vec::new().into_iter(),
+            external: state.external.into_iter(),
+            next_id: 0
+        }
+    }
+}
+
+impl<'a, S: BuildHasher, P: InternerPolicy> Interner<'a, Box<str>, S, P> {
+    /// Intern a string slice.
+    ///
+    /// Works like [`intern`](Self::intern), but the string is only copied
+    /// into the interner if no equal string is interned yet.
+    pub fn intern_str(&self, s: &str) -> Intern<'_, str> {
+        let boxed = self.intern_ref(s);
+        // SAFETY: the boxed string is never mutated nor moved,
+        // therefore neither are the bytes it points to
+        unsafe { Intern::from_raw(&**boxed.0.get_ref()) }
+    }
+}
+
+impl<'a, T: 'a + Eq + Hash + Clone, S: BuildHasher, P: InternerPolicy> Interner<'a, Box<[T]>, S, P> {
+    /// Intern a slice.
+    ///
+    /// Works like [`intern`](Self::intern), but the slice is only copied
+    /// into the interner if no equal slice is interned yet.
+    pub fn intern_slice(&self, s: &[T]) -> Intern<'_, [T]> {
+        let boxed = self.intern_ref(s);
+        // SAFETY: the boxed slice is never mutated nor moved,
+        // therefore neither are the items it points to
+        unsafe { Intern::from_raw(&**boxed.0.get_ref()) }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, S: BuildHasher, P: InternerPolicy> Interner<'a, std::path::PathBuf, S, P> {
+    /// Intern a path slice.
+    ///
+    /// Works like [`intern`](Self::intern), but the path is only copied
+    /// into the interner if no equal path is interned yet.
+    ///
+    /// ```
+    /// use std::path::{Path, PathBuf};
+    /// use libintern::Interner;
+    ///
+    /// let interner = Interner::new();
+    /// let main = interner.intern_path(Path::new("src/main.rs"));
+    /// assert_eq!(interner.intern(PathBuf::from("src/main.rs")), main);
+    /// ```
+    pub fn intern_path(&self, path: &std::path::Path) -> Intern<'_, std::path::PathBuf> {
+        self.intern_ref(path)
+    }
+}
+
+/// Statistics about the storage of an [`Interner`], see [`Interner::stats`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InternerStats {
+    /// The number of holders in the holder chain
+    pub holders: usize,
+    /// The total number of items the holders have room for
+    pub capacity: usize,
+    /// The number of distinct items held
+    pub items: usize,
+    /// The number of bytes taken by the held items, i.e. `size_of::<T>() * items`
+    pub item_bytes: usize
+}
+
+/// A stable integer id of an interned item.
+///
+/// Symbols are assigned densely in insertion order, starting from zero,
+/// and never change for the lifetime of the [`Interner`] which produced them.
+/// Use [`Interner::resolve`] to get a reference to the item back.
+///
+/// Symbols are ordered by their ids, i.e. by the insertion order of the items.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Symbol(pub u32);
+
+/// A recorded state of an [`Interner`], see [`Interner::snapshot`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InternerSnapshot {
+    items: usize
+}
+
+/// The result of [`Interner::intern_entry`].
+#[derive(PartialEq, Eq, Debug)]
+pub enum InternEntry<'a, T: ?Sized> {
+    /// An equal item was already interned, the given item was dropped
+    Occupied(Intern<'a, T>),
+    /// No equal item was interned, the given item is now held by the interner
+    Vacant(Intern<'a, T>)
+}
+
+impl<'a, T: ?Sized> Clone for InternEntry<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+// Hand implemented for the same reason as for Intern
+impl<'a, T: ?Sized> Copy for InternEntry<'a, T> {}
+
+impl<'a, T: ?Sized> InternEntry<'a, T> {
+    /// Get the reference to the interned item.
+    pub fn intern(&self) -> Intern<'a, T> {
+        match *self {
+            InternEntry::Occupied(interned) | InternEntry::Vacant(interned) => interned
+        }
+    }
+
+    /// Returns true if the item was newly interned.
+    pub fn is_vacant(&self) -> bool {
+        matches!(self, InternEntry::Vacant(_))
+    }
+}
+
+/// Growth parameters of an [`Interner`], defined once at the type level.
+///
+/// The policy is the last type parameter of an interner, `Interner<T, S, P>`,
+/// see [`Interner::with_policy`]. Unlike the parameters set on an [`InternerBuilder`],
+/// which are stored in every interner, the constants of the policy are known at compile time.
+/// The growth factor must be greater than `1.0`, otherwise creating an interner fails to compile.
+pub trait InternerPolicy {
+    /// The capacity of the first holder, see [`InternerBuilder::initial_capacity`].
+    const BASE: usize;
+    /// By how much the capacity of every next holder grows,
+    /// see [`InternerBuilder::growth_factor`].
+    const GROWTH: f32;
+}
+
+/// The growth parameters used by [`Interner::new`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct DefaultPolicy;
+
+impl InternerPolicy for DefaultPolicy {
+    const BASE: usize = BEGIN_INTERNER_CAPACITY;
+    const GROWTH: f32 = INTERNER_CAPACITY_DELTA;
+}
+
+/// A builder for an [`Interner`] with custom growth parameters.
+///
+/// ```
+/// use libintern::InternerBuilder;
+///
+/// let interner = InternerBuilder::new()
+///     .initial_capacity(4)
+///     .growth_factor(2.0)
+///     .build();
+/// let a = interner.intern('a');
+/// assert_eq!(*a, 'a');
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct InternerBuilder {
+    initial_capacity: usize,
+    /// The growth factor, if it's not the one of the default policy
+    growth_factor: Option<f32>,
+    expected_items: usize,
+    linear_threshold: usize,
+    boxed: bool
+}
+
+impl InternerBuilder {
+    /// Create a builder with the default growth parameters.
+    pub fn new() -> Self {
+        Self {
+            initial_capacity: BEGIN_INTERNER_CAPACITY,
+            growth_factor: None,
+            expected_items: 0,
+            linear_threshold: LINEAR_LOOKUP_THRESHOLD,
+            boxed: false
+        }
+    }
+
+    /// Set the capacity of the first holder.
+    ///
+    /// The first holder always has room for at least one item.
+    pub fn initial_capacity(mut self, capacity: usize) -> Self {
+        self.initial_capacity = capacity;
+        self
+    }
+
+    /// Set by how much the capacity of every next holder grows.
+    ///
+    /// # Panics
+    /// Panics if the factor isn't greater than `1.0`.
+    pub fn growth_factor(mut self, factor: f32) -> Self {
+        assert!(factor > 1.0, "the growth factor must be greater than 1.0");
+        self.growth_factor = Some(factor);
+        self
+    }
+
+    /// Set how many items are expected to be interned in total,
+    /// see [`Interner::set_expected_items`].
+    pub fn expected_items(mut self, expected_items: usize) -> Self {
+        self.expected_items = expected_items;
+        self
+    }
+
+    /// Set up to how many items are looked up by a linear scan.
+    ///
+    /// Scanning a handful of items is faster than looking them up in a hash index,
+    /// so the index is only built once this many items are interned.
+    /// Zero means the index is always used. The default is 16.
+    pub fn linear_threshold(mut self, threshold: usize) -> Self {
+        self.linear_threshold = threshold;
+        self
+    }
+
+    /// Box every item separately instead of moving it into the holders,
+    /// see [`Interner::new_boxed`].
+    ///
+    /// The growth parameters don't matter then.
+    pub fn boxed(mut self) -> Self {
+        self.boxed = true;
+        self
+    }
+
+    /// Create an interner with the configured growth parameters.
+    pub fn build<'a, T: 'a + Eq>(self) -> Interner<'a, T> {
+        self.build_with_hasher(DefaultHashBuilder::default())
+    }
+
+    /// Create an interner with the configured growth parameters
+    /// which uses the given hasher to hash the items.
+    pub fn build_with_hasher<'a, T: 'a + Eq, S>(self, hash_builder: S) -> Interner<'a, T, S> {
+        // The holders of a boxed interner stay empty
+        let initial_capacity = if self.boxed { 1 } else { self.initial_capacity };
+        let mut interner = Interner::with_config(initial_capacity, self.growth_factor, hash_builder);
+        let state = interner.state.get_mut();
+        state.expected_items = self.expected_items;
+        state.set_linear_threshold(self.linear_threshold);
+        state.boxed = self.boxed;
+        interner
+    }
+}
+
+impl Default for InternerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A wrapper around a vector, which guarantees that
+/// the vector will never grow, thus the addresses (pointers)
+/// of (to) its items will never change
+struct InternedItemHolder<T> {
+    items: Vec<T>,
+    /// Whether the capacity was requested exactly, see [`Interner::reserve_exact`],
+    /// instead of following the growth of the holders
+    exact: bool,
+    /// The address and the capacity of the buffer of the items at creation,
+    /// see [`debug_assert_not_reallocated`](Self::debug_assert_not_reallocated)
+    #[cfg(debug_assertions)]
+    origin: (usize, usize)
+}
+
+impl<T> InternedItemHolder<T> {
+    fn new(capacity: usize) -> Self {
+        let items = Vec::with_capacity(capacity);
+        Self {
+            #[cfg(debug_assertions)]
+            origin: (items.as_ptr() as usize, items.capacity()),
+            items,
+            exact: false
+        }
+    }
+
+    /// Create a holder whose capacity doesn't follow the growth of the holders.
+    fn new_exact(capacity: usize) -> Self {
+        Self { exact: true, ..Self::new(capacity) }
+    }
+
+    /// Make sure the buffer of the items was never reallocated.
+    ///
+    /// The references to the held items rely on that, so in debug builds,
+    /// any change which would accidentally let a holder grow is caught here.
+    #[inline]
+    fn debug_assert_not_reallocated(&self) {
+        #[cfg(debug_assertions)]
+        assert_eq!(
+            (self.items.as_ptr() as usize, self.items.capacity()),
+            self.origin,
+            "an interned item holder was reallocated"
+        );
+    }
+
+    /// Try to add an item to the holder.
+    ///
+    /// If there's enough space for the item, succeed and return Ok(())
+    /// If there's not enough space in the holder,
+    ///  it returns Err(the_item), to prevent dropping the value
+    fn try_push(&mut self, item: T) -> Result<(), T> {
+        if self.items.len() == self.items.capacity() {
+            Err(item)
+        } else {
+            self.items.push(item);
+            Ok(())
+        }
+    }
+}
+
+/// A reference to an interned item.
+///
+/// The main advantage of this type over just
+/// an immutable reference is that its pointer is guaranteed to be unique
+/// within a single [`Interner`]. Therefore comparisons
+/// are very cheap.
+///
+/// # Note about hashing
+///
+/// This wrapper implements [`PartialEq`] by comparing its inner pointer.
+/// In order to keep consistency, the [`Hash`] trait is also implemented
+/// by hashing the pointer, NOT the inner value. Therefore hashes
+/// of the `Intern<T>` type are different than hashes of the `T`.
+///
+/// # Note about threads
+///
+/// Like a shared reference, an `Intern<T>` is both [`Send`] and [`Sync`] if `T` is [`Sync`].
+/// So even though the [`Interner`] itself can't be shared between threads,
+/// the references it produces can be handed to scoped worker threads.
+///
+/// # Note about layout
+///
+/// An `Intern<T>` has the same layout as a `&T`.
+#[repr(transparent)]
+pub struct Intern<'a, T: 'a + ?Sized>(Pin<&'a T>);
+
+impl<'a, T: ?Sized> Clone for Intern<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+// We must hand implement Copy, because a T: Copy bound is added when using derive
+//
+/// An `Intern` is always `Copy`, no matter whether the item is,
+/// as it's just a reference to the item.
+impl<'a, T: ?Sized> Copy for Intern<'a, T> {}
+
+// Get reference to the inner item
+impl<'a, T: ?Sized> AsRef<T> for Intern<'a, T> {
+    fn as_ref(&self) -> &T {
+        self.0.get_ref()
+    }
+}
+
+// Get the interned string as a string slice
+impl<'a> AsRef<str> for Intern<'a, String> {
+    fn as_ref(&self) -> &str {
+        self.0.get_ref()
+    }
+}
+
+// Get the interned byte buffer as a byte slice
+impl<'a> AsRef<[u8]> for Intern<'a, Vec<u8>> {
+    fn as_ref(&self) -> &[u8] {
+        self.0.get_ref()
+    }
+}
+
+// Get the interned path as a path slice
+#[cfg(feature = "std")]
+impl<'a> AsRef<std::path::Path> for Intern<'a, std::path::PathBuf> {
+    fn as_ref(&self) -> &std::path::Path {
+        self.0.get_ref()
+    }
+}
+
+// Get the interned OS string as an OS string slice
+#[cfg(feature = "std")]
+impl<'a> AsRef<std::ffi::OsStr> for Intern<'a, std::ffi::OsString> {
+    fn as_ref(&self) -> &std::ffi::OsStr {
+        self.0.get_ref()
+    }
+}
+
+impl<'a, T: ?Sized> Deref for Intern<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+// Implement Debug if the item implements Debug
+impl<'a, T: ?Sized + Debug> Debug for Intern<'a, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+// Implement Display if the item implements Display
+impl<'a, T: ?Sized + Display> Display for Intern<'a, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+// Format the address of the item, which identifies the distinct item
+impl<'a, T: ?Sized> core::fmt::Pointer for Intern<'a, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Pointer::fmt(&self.0.get_ref(), f)
+    }
+}
+
+// Implement PartialEq
+// 
+// Because we can guarantee that if the item is the same,
+// the item's place in memory, therefore the pointer is the same,
+// we can just compare values of the pointers, not the items themselves 
+//
+// Zero-sized items all share the same address, but as all the values
+// of a zero-sized type are equal, only one of them is ever held anyway
+impl<'a, T: ?Sized> PartialEq for Intern<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::eq(self.as_ref() as *const _, other.as_ref() as *const _)
+    }
+}
+
+impl<'a, T: ?Sized> Eq for Intern<'a, T> {}
+
+/// Compare the interned item with a raw value.
+///
+/// Unlike comparing two `Intern`s, this compares the values themselves.
+///
+/// ```
+/// use libintern::Interner;
+///
+/// let interner = Interner::new();
+/// assert!(interner.intern('a') == 'a');
+/// ```
+impl<'a, T: ?Sized + PartialEq> PartialEq<T> for Intern<'a, T> {
+    fn eq(&self, other: &T) -> bool {
+        self.0.get_ref() == other
+    }
+}
+
+// Implement Hash
+// 
+/// To keep consistency with [`PartialEq`], we hash the pointer, not the value.
+/// This also makes hashing cheap no matter how large the item is,
+/// e.g. when an `Intern` is used as a key of a `HashMap`.
+///
+/// For the same reason, an `Intern` doesn't implement [`Borrow`]:
+/// a borrowed form has to hash like the `Intern` itself.
+/// To look up a map by the items or their borrowed forms, key it by [`ByValue`] instead.
+impl<'a, T: ?Sized> Hash for Intern<'a, T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        core::ptr::hash(self.0.get_ref(), state)
+    }
+}
+
+// Implement PartialOrd and Ord if the item implements it
+//
+/// Unlike [`PartialEq`], this compares the values, NOT the pointers.
+/// Because equal items are interned to the same pointer,
+/// this ordering is still consistent with the pointer-based equality.
+impl<'a, T: ?Sized + PartialOrd> PartialOrd for Intern<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.as_ref().partial_cmp(other.as_ref())
+    }
+}
+
+/// Unlike [`Eq`], this compares the values, NOT the pointers.
+/// Because equal items are interned to the same pointer,
+/// this ordering is still consistent with the pointer-based equality.
+impl<'a, T: ?Sized + Ord> Ord for Intern<'a, T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_ref().cmp(other.as_ref())
+    }
+}
+
+impl<'a, T: ?Sized> Intern<'a, T> {
+    /// Create a new [`Intern`] type from a raw pointer.
+    ///
+    /// # Safety
+    /// The caller must guarantee that the pointed value is in fact
+    /// owned by an [`Interner`] which is still in scope (i.e. not dropped)
+    /// and that the pointer will not be mutated and/or moved.
+    pub unsafe fn from_raw(ptr: *const T) -> Self {
+        Intern(Pin::new_unchecked(ptr.as_ref().unwrap()))
+    }
+
+    /// Turn this into a plain reference to the interned item.
+    ///
+    /// The reference lives as long as this [`Intern`] would,
+    /// but it's compared and hashed by value, not by address.
+    pub fn into_ref(self) -> &'a T {
+        self.0.get_ref()
+    }
+
+    /// Get the pointer to the interned item.
+    ///
+    /// The pointer is unique for every distinct item and doesn't change
+    /// for as long as the [`Interner`] holding the item is alive.
+    pub fn as_ptr(&self) -> *const T {
+        self.0.get_ref()
+    }
+
+    /// Get the address of the interned item.
+    ///
+    /// Like [`as_ptr`](Self::as_ptr), the address is unique for every distinct item
+    /// and doesn't change for as long as the [`Interner`] holding the item is alive,
+    /// so it can be used as a cheap key in place of the [`Intern`] itself.
+    pub fn addr(&self) -> usize {
+        self.as_ptr() as *const () as usize
+    }
+
+    /// Compare the interned items by value.
+    ///
+    /// Unlike `==`, this also works for references produced by different interners,
+    /// which are never equal even if the items they point to are.
+    pub fn value_eq(&self, other: &Intern<'_, T>) -> bool
+    where
+        T: PartialEq
+    {
+        self.as_ref() == other.as_ref()
+    }
+
+    /// Compare the interned items by their addresses.
+    ///
+    /// This is a total order consistent with `==`, but it's not semantically meaningful:
+    /// the address order of the items is neither their value order nor their insertion order.
+    /// It's useful e.g. to sort references for cache-friendly access.
+    /// The [`Ord`] implementation of [`Intern`] compares the values instead.
+    // The metadata of the pointers is compared as well,
+    // just like in the equality of [`Intern`], so that the two are consistent
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    pub fn cmp_addr(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_ptr().cmp(&other.as_ptr())
+    }
+
+    /// Wrap this reference so that it's ordered by address, see [`ByAddress`].
+    pub fn by_address(self) -> ByAddress<'a, T> {
+        ByAddress(self)
+    }
+
+    /// Wrap this reference so that it's compared and hashed by value, see [`ByValue`].
+    pub fn by_value(self) -> ByValue<'a, T> {
+        ByValue(self)
+    }
+}
+
+/// A wrapper around [`Intern`] which is ordered by the address of the item, not its value.
+///
+/// Comparing addresses is very cheap and doesn't require `T: Ord`,
+/// which makes this a good key for a `BTreeMap` when the order itself
+/// doesn't matter. The order is consistent with the equality of [`Intern`],
+/// but it has no meaning otherwise, e.g. it's NOT the insertion order.
+pub struct ByAddress<'a, T: 'a + ?Sized>(pub Intern<'a, T>);
+
+impl<'a, T: ?Sized> Clone for ByAddress<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T: ?Sized> Copy for ByAddress<'a, T> {}
+
+impl<'a, T: ?Sized + Debug> Debug for ByAddress<'a, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'a, T: ?Sized> PartialEq for ByAddress<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<'a, T: ?Sized> Eq for ByAddress<'a, T> {}
+
+impl<'a, T: ?Sized> Hash for ByAddress<'a, T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<'a, T: ?Sized> PartialOrd for ByAddress<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, T: ?Sized> Ord for ByAddress<'a, T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.cmp_addr(&other.0)
+    }
+}
+
+/// A wrapper around [`Intern`] which is compared and hashed by the value of the item.
+///
+/// [`Intern`] itself hashes the address of the item, so it can't be looked up
+/// in a map by a borrowed form of the item. This wrapper hashes the item instead,
+/// which makes it a key of a map which can be looked up by value:
+///
+/// ```
+/// use std::collections::HashMap;
+/// use libintern::Interner;
+///
+/// let interner = Interner::new();
+/// let mut counts = HashMap::new();
+/// counts.insert(interner.intern(String::from("foo")).by_value(), 1);
+/// assert_eq!(counts.get("foo"), Some(&1));
+/// ```
+///
+/// Comparing values is slower than comparing addresses,
+/// but it also works for references produced by different interners.
+pub struct ByValue<'a, T: 'a + ?Sized>(pub Intern<'a, T>);
+
+impl<'a, T: ?Sized> Clone for ByValue<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T: ?Sized> Copy for ByValue<'a, T> {}
+
+impl<'a, T: ?Sized + Debug> Debug for ByValue<'a, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'a, T: ?Sized + PartialEq> PartialEq for ByValue<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.value_eq(&other.0)
+    }
+}
+
+impl<'a, T: ?Sized + Eq> Eq for ByValue<'a, T> {}
+
+impl<'a, T: ?Sized + Hash> Hash for ByValue<'a, T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.as_ref().hash(state)
+    }
+}
+
+impl<'a, T: ?Sized + PartialOrd> PartialOrd for ByValue<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.0.as_ref().partial_cmp(other.0.as_ref())
+    }
+}
+
+impl<'a, T: ?Sized + Ord> Ord for ByValue<'a, T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.as_ref().cmp(other.0.as_ref())
+    }
+}
+
+impl<'a, T: ?Sized> Borrow<T> for ByValue<'a, T> {
+    fn borrow(&self) -> &T {
+        self.0.as_ref()
+    }
+}
+
+impl<'a> Borrow<str> for ByValue<'a, String> {
+    fn borrow(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+impl<'a> Borrow<str> for ByValue<'a, Box<str>> {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'a, T> Borrow<[T]> for ByValue<'a, Vec<T>> {
+    fn borrow(&self) -> &[T] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> Borrow<std::path::Path> for ByValue<'a, std::path::PathBuf> {
+    fn borrow(&self) -> &std::path::Path {
+        &self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> Borrow<std::ffi::OsStr> for ByValue<'a, std::ffi::OsString> {
+    fn borrow(&self) -> &std::ffi::OsStr {
+        &self.0
+    }
+}
+
+/// An iterator over the items of an [`Interner`], see [`Interner::iter`].
+pub struct Iter<'a, 'intern, T: core::cmp::Eq, S = DefaultHashBuilder, P = DefaultPolicy> {
+    interner: &'a Interner<'intern, T, S, P>,
+    next_id: usize,
+    /// The number of items when the iterator was created, the items interned since aren't yielded
+    end: usize
+}
+
+impl<'a, 'intern, T: core::cmp::Eq, S, P: InternerPolicy> Iterator for Iter<'a, 'intern, T, S, P> {
+    type Item = Intern<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_id == self.end {
+            return None
+        }
+        let item = self.interner.state.borrow().symbols[self.next_id];
+        self.next_id += 1;
+        // SAFETY: the symbol table only contains pointers to the held items
+        Some(unsafe { self.interner.transmute_held_item(item) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.next_id;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, 'intern, T: core::cmp::Eq, S, P: InternerPolicy> ExactSizeIterator for Iter<'a, 'intern, T, S, P> {}
+
+impl<'a, 'intern, T: core::cmp::Eq, S, P: InternerPolicy> IntoIterator for &'a Interner<'intern, T, S, P> {
+    type Item = Intern<'a, T>;
+    type IntoIter = Iter<'a, 'intern, T, S, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator which moves the items out of an [`Interner`],
+/// see [`Interner::into_iter`](struct.Interner.html#method.into_iter).
+pub struct IntoIter<T> {
+    /// The holders which weren't reached yet
+    holders: alloc::vec::IntoIter<InternedItemHolder<T>>,
+    /// The items of the current holder
+    current: alloc::vec::IntoIter<T>,
+    /// The external items which weren't reached yet
+    external: alloc::vec::IntoIter<(Symbol, ExternalItem<T>)>,
+    /// The symbol of the next item
+    next_id: usize
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let id = self.next_id;
+            self.next_id += 1;
+            match self.external.as_slice().first() {
+                Some((symbol, _)) if symbol.0 as usize == id => match self.external.next().unwrap().1 {
+                    ExternalItem::Boxed(item) => return Some(*item),
+                    // The other external items aren't owned by the interner
+                    _ => continue
+                },
+                _ => ()
+            }
+            // Items are always added to the last holder,
+            // so the holders hold the items in insertion order
+            loop {
+                if let Some(item) = self.current.next() {
+                    return Some(item)
+                }
+                self.current = self.holders.next()?.items.into_iter();
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let boxed = self.external.as_slice().iter()
+            .filter(|(_, item)| matches!(item, ExternalItem::Boxed(_)))
+            .count();
+        let remaining = boxed + self.current.len() + self.holders.as_slice().iter().map(|holder| holder.items.len()).sum::<usize>();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::hash_map::DefaultHasher, hash::{Hash, Hasher}};
+    use super::{DefaultPolicy, Intern, InternEntry, InternedItemHolder, Interner, InternerBuilder, InternerPolicy, InternerStats, Symbol, LINEAR_LOOKUP_THRESHOLD};
+
+    #[test]
+    fn interned_item_holder_test() {
+        let mut holder = InternedItemHolder::new(4); // size four
+
+        // Add an item
+        assert!(holder.try_push('a').is_ok());
+        assert!(holder.items.len() == 1);
+        assert!(holder.items.capacity() == 4);
+        // Save the address of the item
+        let first_item_address = holder.items.first().unwrap() as *const _ as usize;
+
+        // Add another item
+        assert!(holder.try_push('b').is_ok());
+        assert!(holder.items.len() == 2);
+        assert!(holder.items.capacity() == 4);
+        // Make sure the address of the first one didn't change
+        assert_eq!(
+            holder.items.first().unwrap() as *const _ as usize,
+            first_item_address
+        );
+        let second_item_address = holder.items.get(1).unwrap() as *const _ as usize;
+
+        // Add two more items
+        assert!(holder.try_push('c').is_ok());
+        assert!(holder.try_push('d').is_ok());
+        assert!(holder.items.len() == 4);
+        assert!(holder.items.capacity() == 4);
+        // Make sure the addresses didn't change
+        assert_eq!(
+            holder.items.first().unwrap() as *const _ as usize,
+            first_item_address
+        );
+        assert_eq!(
+            holder.items.get(1).unwrap() as *const _ as usize,
+            second_item_address
+        );
+
+        // Try to add more items
+        assert_eq!(holder.try_push('e'), Err('e'));
+        assert_eq!(holder.try_push('f'), Err('f'));
+        assert!(holder.items.len() == 4);
+        assert!(holder.items.capacity() == 4);
+        // Make sure the addresses didn't change
+        assert_eq!(
+            holder.items.first().unwrap() as *const _ as usize,
+            first_item_address
+        );
+        assert_eq!(
+            holder.items.get(1).unwrap() as *const _ as usize,
+            second_item_address
+        );
+
+        // Try to dereference the addresses, just to be sure
+        assert_eq!(
+            unsafe { *(first_item_address as *const char) },
+            'a');
+        assert_eq!(
+            unsafe { *(second_item_address as *const char) },
+            'b');
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "an interned item holder was reallocated")]
+    fn interned_item_holder_reallocated_test() {
+        let mut holder = InternedItemHolder::new(1);
+        assert!(holder.try_push('a').is_ok());
+        holder.debug_assert_not_reallocated();
+        // Bypass `try_push`, which would refuse to grow the holder
+        holder.items.push('b');
+        holder.debug_assert_not_reallocated();
+    }
+
+    #[test]
+    fn interner_test() {
+        let int = Interner::new();
+        // Intern some things
+        let ref_a1 = int.intern('a');
+        let ref_b = int.intern('b');
+        let ref_a2 = int.intern('a');
+        // After this, only TWO items should be interned 'a' and 'b'. The second 'a' should have been discarded
+        assert_eq!(int.state.borrow().holders.len(), 1);
+        assert_eq!(int.state.borrow().holders[0].items.len(), 2);
+        // Now check that the addresses of ref_a1 and ref_a2 are equal
+        assert!(std::ptr::eq(ref_a1.as_ref(), ref_a2.as_ref()));
+        assert!(!std::ptr::eq(ref_a1.as_ref(), ref_b.as_ref()));
+
+        let ref_b2 = int.intern('b');
         let _ref_c = int.intern('c');
         assert_eq!(ref_b, ref_b2);
     }
 
     #[test]
-    fn intern_impl_test() {
+    fn intern_impl_test() {
+        let int = Interner::new();
+        let a1 = int.intern('a');
+        let a2 = int.intern('a');
+        let x = int.intern('x');
+
+        // AsRef
+        assert_eq!(a1.as_ref(), &'a');
+        // Deref
+        assert_eq!(*a1, 'a');
+        // TODO: Debug and Display test
+        // PartialEq
+        assert_eq!(a1, a2);
+        assert_ne!(a1, x);
+        // Hash
+        fn hash_of<H: Hash>(value: H) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+        assert_eq!(hash_of(a1), hash_of(a2));
+        // The pointer is hashed, not the value
+        assert_eq!(hash_of(a1), hash_of(a1.as_ptr()));
+        assert_ne!(hash_of(a1), hash_of('a'));
+    }
+
+    #[test]
+    fn intern_addr_test() {
+        let int = Interner::new();
+        let a = int.intern(String::from("a"));
+        let b = int.intern(String::from("b"));
+        assert_eq!(a.as_ptr(), a.as_ref() as *const String);
+        assert_eq!(a.addr(), a.as_ptr() as usize);
+        assert_ne!(a.addr(), b.addr());
+
+        // The address stays the same while more items are interned
+        for i in 0..1000 {
+            int.intern(i.to_string());
+        }
+        assert_eq!(int.intern(String::from("a")).addr(), a.addr());
+    }
+
+    #[test]
+    fn intern_from_addr_test() {
+        let int = Interner::with_capacity(1);
+        let a = int.intern(String::from("a"));
+        let b = int.intern(String::from("b"));
+        // SAFETY: the addresses were produced by this interner
+        unsafe {
+            assert_eq!(int.intern_from_addr(a.addr()), a);
+            assert_eq!(int.intern_from_addr(b.addr()), b);
+        }
+        assert!(int.state.borrow().holds_address(b.addr()));
+        assert!(!int.state.borrow().holds_address(b.addr() + 1));
+        let outside = String::from("b");
+        assert!(!int.state.borrow().holds_address(&outside as *const String as usize));
+    }
+
+    #[test]
+    fn intern_by_address_test() {
+        // A type without any ordering
+        #[derive(PartialEq, Eq, Hash, Debug)]
+        struct Unordered(u32);
+
+        let int = Interner::new();
+        let mut map = std::collections::BTreeMap::new();
+        for i in 0..100 {
+            map.insert(int.intern(Unordered(i % 10)).by_address(), i);
+        }
+        assert_eq!(map.len(), 10);
+        assert_eq!(map[&int.intern(Unordered(3)).by_address()], 93);
+
+        let a = int.intern(Unordered(1)).by_address();
+        let b = int.intern(Unordered(2)).by_address();
+        assert_eq!(a.cmp(&b), a.0.as_ptr().cmp(&b.0.as_ptr()));
+        assert_eq!(a.cmp(&a), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn intern_string_borrow_test() {
+        fn takes_str<S: AsRef<str>>(s: S) -> usize {
+            s.as_ref().len()
+        }
+
+        let int = Interner::new();
+        let hello = int.intern(String::from("hello"));
+        assert_eq!(takes_str(hello), 5);
+
+        // Maps are looked up by the borrowed form through ByValue
+        let map: std::collections::HashMap<_, _> = vec![(hello.by_value(), 1)].into_iter().collect();
+        assert_eq!(map.get("hello"), Some(&1));
+        assert_eq!(map.get(&String::from("hello")), Some(&1));
+    }
+
+    #[test]
+    fn intern_eq_value_test() {
+        let int = Interner::new();
+        let a = int.intern('a');
+        assert!(a == 'a');
+        assert!(a != 'b');
+        let strings = Interner::new();
+        assert_eq!(strings.intern(String::from("abc")), String::from("abc"));
+    }
+
+    #[test]
+    fn intern_bytes_borrow_test() {
+        fn takes_bytes<B: AsRef<[u8]>>(bytes: B) -> usize {
+            bytes.as_ref().len()
+        }
+
+        let int = Interner::new();
+        let bytes = int.intern(vec![1u8, 2, 3]);
+        assert_eq!(takes_bytes(bytes), 3);
+
+        let map: std::collections::HashMap<_, _> = vec![(bytes.by_value(), 1)].into_iter().collect();
+        assert_eq!(map.get(&[1u8, 2, 3][..]), Some(&1));
+    }
+
+    #[test]
+    fn intern_pointer_fmt_test() {
+        let int = Interner::new();
+        let a = int.intern(String::from("a"));
+        assert_eq!(format!("{:p}", a), format!("{:p}", a.as_ptr()));
+        assert_eq!(format!("{:p}", a), format!("{:p}", int.intern(String::from("a"))));
+        assert_ne!(format!("{:p}", a), format!("{:p}", int.intern(String::from("b"))));
+    }
+
+    #[test]
+    fn intern_value_eq_test() {
+        let first = Interner::new();
+        let second = Interner::new();
+        let a = first.intern('a');
+        let other_a = second.intern('a');
+        assert_ne!(a, other_a);
+        assert!(a.value_eq(&other_a));
+        assert!(!a.value_eq(&second.intern('b')));
+    }
+
+    #[test]
+    fn intern_hash_set_test() {
+        let int = Interner::with_capacity(1);
+        let mut set = std::collections::HashSet::new();
+        // Intern every item several times, across many holders
+        for round in 0..3 {
+            for i in 0..200 {
+                set.insert(int.intern(i.to_string()));
+            }
+            assert_eq!(set.len(), 200, "round {}", round);
+        }
+        assert!(int.stats().holders > 10);
+        assert!(set.contains(&int.intern(String::from("123"))));
+        for item in &set {
+            assert_eq!(set.get(&int.intern(String::clone(item))), Some(item));
+        }
+    }
+
+    #[test]
+    fn intern_cmp_addr_test() {
+        use std::cmp::Ordering;
+
+        let int = Interner::new();
+        let mut items: Vec<_> = (0..10).rev().map(|i| int.intern(i)).collect();
+        let a = items[0];
+        assert_eq!(a.cmp_addr(&int.intern(9)), Ordering::Equal);
+        items.sort_by(Intern::cmp_addr);
+        assert!(items.windows(2).all(|pair| pair[0].addr() < pair[1].addr()));
+        assert_eq!(items[0].cmp_addr(&items[1]), items[0].by_address().cmp(&items[1].by_address()));
+    }
+
+    #[test]
+    fn intern_copy_test() {
+        fn assert_copy<C: Copy>() {}
+
+        // None of these item types are Copy
+        assert_copy::<Intern<'static, String>>();
+        assert_copy::<Intern<'static, str>>();
+        assert_copy::<Intern<'static, [Vec<u8>]>>();
+        assert_copy::<super::ByAddress<'static, String>>();
+
+        let int = Interner::new();
+        let a = int.intern(String::from("a"));
+        let copy = a;
+        assert_eq!(a, copy);
+    }
+
+    #[test]
+    fn intern_send_sync_test() {
+        fn assert_send_sync<V: Send + Sync>() {}
+        fn shorten<'short>(int: &'short Interner<'static, String>) -> &'short Interner<'short, String> {
+            // The interner is covariant in its lifetime
+            int
+        }
+
+        assert_send_sync::<Intern<'static, String>>();
+        assert_send_sync::<Intern<'static, str>>();
+
+        let int = Interner::new();
+        let a = shorten(&int).intern(String::from("a"));
+        let len = std::thread::scope(|scope| scope.spawn(move || a.len()).join().unwrap());
+        assert_eq!(len, 1);
+    }
+
+    #[test]
+    fn intern_into_ref_test() {
+        // The reference outlives the Intern it came from
+        fn unwrap_intern(item: Intern<'_, String>) -> &String {
+            item.into_ref()
+        }
+
+        let int = Interner::new();
+        let reference = unwrap_intern(int.intern(String::from("a")));
+        assert_eq!(reference, "a");
+        assert!(std::ptr::eq(reference, int.intern(String::from("a")).as_ptr()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn intern_path_test() {
+        use std::{collections::HashMap, ffi::{OsStr, OsString}, path::{Path, PathBuf}};
+
+        let paths = Interner::new();
+        let main = paths.intern_path(Path::new("src/main.rs"));
+        assert_eq!(paths.intern_path(Path::new("src/main.rs")), main);
+        assert_eq!(paths.intern(PathBuf::from("src/main.rs")), main);
+        assert_eq!(paths.len(), 1);
+        let path: &Path = main.as_ref();
+        assert_eq!(path.extension(), Some(OsStr::new("rs")));
+
+        let names: Interner<OsString> = Interner::new();
+        let name = names.intern_ref(OsStr::new("main"));
+        let name_str: &OsStr = name.as_ref();
+        assert_eq!(name_str, OsStr::new("main"));
+        assert_eq!(names.intern(OsString::from("main")), name);
+
+        // Maps are looked up by the borrowed forms through ByValue
+        let by_path: HashMap<_, _> = vec![(main.by_value(), 1)].into_iter().collect();
+        assert_eq!(by_path.get(Path::new("src/main.rs")), Some(&1));
+        let by_name: HashMap<_, _> = vec![(name.by_value(), 1)].into_iter().collect();
+        assert_eq!(by_name.get(OsStr::new("main")), Some(&1));
+    }
+
+    #[test]
+    fn intern_layout_test() {
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(size_of::<Intern<'static, u64>>(), size_of::<&u64>());
+        assert_eq!(align_of::<Intern<'static, u64>>(), align_of::<&u64>());
+        // Wide references too
+        assert_eq!(size_of::<Intern<'static, str>>(), size_of::<&str>());
+        assert_eq!(size_of::<Intern<'static, [u8]>>(), size_of::<&[u8]>());
+        // The null pointer niche is kept
+        assert_eq!(size_of::<Option<Intern<'static, u64>>>(), size_of::<&u64>());
+
+        let int = Interner::new();
+        let a = int.intern(7u64);
+        // SAFETY: Intern is transparent over a reference
+        let reference: &u64 = unsafe { std::mem::transmute::<Intern<u64>, &u64>(a) };
+        assert!(std::ptr::eq(reference, a.as_ptr()));
+    }
+
+    #[test]
+    fn intern_by_value_test() {
+        use std::collections::{BTreeSet, HashMap};
+
+        let int = Interner::new();
+        let foo = int.intern(String::from("foo"));
+        let mut map = HashMap::new();
+        map.insert(foo.by_value(), 1);
+        map.insert(int.intern(String::from("bar")).by_value(), 2);
+        assert_eq!(map.get("foo"), Some(&1));
+        assert_eq!(map.get(&String::from("bar")), Some(&2));
+        assert_eq!(map.get("baz"), None);
+
+        // References from different interners are equal by value
+        let other = Interner::new();
+        assert_eq!(other.intern(String::from("foo")).by_value(), foo.by_value());
+        assert_eq!(map.get(&other.intern(String::from("foo")).by_value()), Some(&1));
+
+        let boxed: Interner<Box<str>> = Interner::new();
+        let set: BTreeSet<_> = ["b", "a"].iter().map(|&s| boxed.intern(Box::from(s)).by_value()).collect();
+        assert!(set.contains("a"));
+        assert_eq!(set.iter().map(|item| &**item.0).collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn intern_pattern_test() {
+        #[derive(PartialEq, Eq, Hash, Debug)]
+        enum Token {
+            Keyword(&'static str),
+            Number(u64),
+            Eof
+        }
+
+        let chars = Interner::new();
+        let c = chars.intern('q');
+        let kind = match *c {
+            'a'..='z' => "lower",
+            _ => "other"
+        };
+        assert_eq!(kind, "lower");
+        assert!(matches!(*c, 'q'));
+
+        let tokens = Interner::new();
+        let keyword = tokens.intern(Token::Keyword("fn"));
+        let number = tokens.intern(Token::Number(42));
+        // Matching the place behind the reference doesn't move out of it
+        match *keyword {
+            Token::Keyword(name) => assert_eq!(name, "fn"),
+            _ => unreachable!()
+        }
+        if let Token::Number(ref n) = *number {
+            assert_eq!(*n, 42);
+        } else {
+            unreachable!()
+        }
+        // Or match a plain reference with the default binding modes
+        assert!(matches!(number.into_ref(), Token::Number(n) if *n == 42));
+        assert!(matches!(&*tokens.intern(Token::Eof), Token::Eof));
+    }
+
+    #[test]
+    fn intern_ord_test() {
+        let int = Interner::new();
+        // Intern in a different order than the values' order
+        let c = int.intern('c');
+        let a = int.intern('a');
+        let b = int.intern('b');
+
+        assert!(a < b && b < c);
+        assert_eq!(a.cmp(&int.intern('a')), std::cmp::Ordering::Equal);
+
+        let mut sorted = vec![c, a, b, a];
+        sorted.sort();
+        assert_eq!(sorted, vec![a, a, b, c]);
+
+        let map: std::collections::BTreeMap<_, _> = vec![(c, 3), (a, 1), (b, 2)].into_iter().collect();
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![a, b, c]);
+    }
+
+    #[test]
+    fn interner_iter_test() {
+        let int = Interner::new();
+        for i in 0..100 {
+            int.intern(i);
+        }
+
+        let collected: Vec<i32> = int.iter().map(|i| *i).collect();
+
+        assert_eq!(
+            collected,
+            (0..100).collect::<Vec<i32>>()
+        );
+
+        // Items interned while iterating aren't yielded
+        let mut iter = int.iter();
+        iter.next();
+        int.intern(100);
+        assert_eq!(iter.len(), 99);
+        assert_eq!(iter.count(), 99);
+    }
+
+    #[test]
+    fn interner_iter_identity_test() {
+        let int = Interner::new();
+        // Iterating an empty interner yields nothing
+        assert_eq!(int.iter().next(), None);
+
+        let interned: Vec<_> = "hello world".chars().map(|c| int.intern(c)).collect();
+        let mut yielded = Vec::new();
+        for item in &int {
+            yielded.push(item);
+        }
+        assert_eq!(yielded.len(), int.len());
+        for item in yielded {
+            assert_eq!(item, int.intern(*item));
+        }
+        assert_eq!(int.iter().len(), 8);
+        assert!(interned.iter().all(|item| int.iter().any(|other| other == *item)));
+    }
+
+    #[test]
+    fn interner_len_test() {
+        let int = Interner::new();
+        // The first holder is pre-allocated, but holds nothing
+        assert_eq!(int.len(), 0);
+        assert!(int.is_empty());
+
+        int.intern('a');
+        int.intern('b');
+        int.intern('a');
+        assert_eq!(int.len(), 2);
+        assert!(!int.is_empty());
+
+        for i in 0..100 {
+            int.intern(char::from_u32(0x100 + i).unwrap());
+        }
+        assert_eq!(int.len(), 102);
+    }
+
+    #[test]
+    fn interner_default_test() {
+        #[derive(Default)]
+        struct Context<'a> {
+            names: Interner<'a, String>
+        }
+
+        let ctx = Context::default();
+        assert!(ctx.names.is_empty());
+        let a = ctx.names.intern(String::from("a"));
+        assert_eq!(ctx.names.intern(String::from("a")), a);
+    }
+
+    #[test]
+    fn interner_symbol_test() {
+        let int = Interner::new();
+        let a = int.intern_symbol('a');
+        let b = int.intern_symbol('b');
+        assert_eq!(a, Symbol(0));
+        assert_eq!(b, Symbol(1));
+        assert_eq!(int.intern_symbol('a'), a);
+        assert_eq!(int.resolve(a), int.intern('a'));
+        assert_eq!(*int.resolve(b), 'b');
+
+        // Symbols stay dense and stable across holder boundaries
+        let symbols: Vec<_> = (0..200).map(|i| int.intern_symbol(char::from_u32(0x100 + i).unwrap())).collect();
+        assert!(int.state.borrow().holders.len() > 1);
+        for (i, symbol) in symbols.iter().enumerate() {
+            assert_eq!(symbol.0 as usize, i + 2);
+            assert_eq!(*int.resolve(*symbol), char::from_u32(0x100 + i as u32).unwrap());
+        }
+        assert_eq!(*int.resolve(a), 'a');
+    }
+
+    #[test]
+    fn interner_intern_str_test() {
+        let int = Interner::new();
+        let hello = int.intern_str("hello");
+        let world = int.intern_str(&String::from("world"));
+        assert_eq!(&*hello, "hello");
+        assert_eq!(int.intern_str("hello"), hello);
+        assert_ne!(hello, world);
+        assert_eq!(int.len(), 2);
+        // Strings interned by value are shared with string slices
+        assert!(std::ptr::eq(&**int.intern(Box::from("world")), &*world));
+        assert_eq!(int.len(), 2);
+    }
+
+    #[test]
+    fn interner_intern_ref_test() {
+        let int: Interner<String> = Interner::new();
+        let a = int.intern_ref("a");
+        assert_eq!(a, int.intern(String::from("a")));
+        assert_eq!(int.intern_ref("a"), a);
+        assert_ne!(int.intern_ref("b"), a);
+        assert_eq!(int.len(), 2);
+
+        let bytes: Interner<Vec<u8>> = Interner::new();
+        let abc = bytes.intern_ref(&b"abc"[..]);
+        assert_eq!(&abc[..], b"abc");
+        assert_eq!(bytes.intern(b"abc".to_vec()), abc);
+    }
+
+    #[test]
+    fn interner_from_iter_test() {
+        let keywords = ["fn", "let", "fn", "match", "let"];
+        let int: Interner<&str> = keywords.iter().copied().collect();
+        assert_eq!(int.len(), 3);
+        assert_eq!(int.iter().map(|k| *k).collect::<Vec<_>>(), vec!["fn", "let", "match"]);
+
+        let expected = Interner::new();
+        for keyword in keywords.iter().copied() {
+            expected.intern(keyword);
+        }
+        for keyword in keywords.iter().copied() {
+            assert_eq!(int.intern_symbol(keyword), expected.intern_symbol(keyword));
+        }
+        assert_eq!(int.len(), expected.len());
+    }
+
+    #[test]
+    fn interner_extend_test() {
+        let mut int = Interner::new();
+        let a = int.intern_symbol('a');
+        int.extend(vec!['b', 'a', 'c']);
+        int.extend("cbd".chars());
+        assert_eq!(int.len(), 4);
+        assert_eq!(int.intern_symbol('a'), a);
+        assert_eq!(int.iter().map(|c| *c).collect::<String>(), "abcd");
+    }
+
+    #[test]
+    fn interner_with_capacity_test() {
+        let int = Interner::with_capacity(1000);
+        for i in 0..1000 {
+            int.intern(i);
+        }
+        assert_eq!(int.state.borrow().holders.len(), 1);
+        assert_eq!(int.len(), 1000);
+
+        // A zero capacity interner is still usable
+        let int = Interner::with_capacity(0);
+        let a = int.intern('a');
+        int.intern('b');
+        assert_eq!(int.intern('a'), a);
+        assert_eq!(int.len(), 2);
+    }
+
+    #[test]
+    fn interner_small_capacity_growth_test() {
+        let int = Interner::with_capacity(1);
+        for i in 0..100 {
+            int.intern(i);
+        }
+        assert_eq!(int.len(), 100);
+        // Every holder must be strictly larger than the previous one
+        let capacities: Vec<_> = int.state.borrow().holders.iter().map(|h| h.items.capacity()).collect();
+        assert_eq!(&capacities[..5], &[1, 2, 3, 4, 6]);
+        assert!(capacities.windows(2).all(|w| w[0] < w[1]));
+        assert!(int.state.borrow().holders.len() < 20);
+    }
+
+    #[test]
+    fn interner_contains_test() {
+        let int = Interner::new();
+        assert!(!int.contains("a"));
+        int.intern(String::from("a"));
+        assert!(int.contains("a"));
+        assert!(int.contains(&String::from("a")));
+        assert!(!int.contains("b"));
+        // Nothing was interned by the lookups
+        assert_eq!(int.len(), 1);
+    }
+
+    #[test]
+    fn interner_get_or_intern_test() {
+        let int = Interner::new();
+        let (a, inserted) = int.get_or_intern('a');
+        assert!(inserted);
+        let (other_a, inserted) = int.get_or_intern('a');
+        assert!(!inserted);
+        assert_eq!(a, other_a);
+        assert!(int.get_or_intern('b').1);
+        assert_eq!(int.len(), 2);
+    }
+
+    #[test]
+    fn interner_intern_slice_test() {
+        let int = Interner::new();
+        let abc = int.intern_slice(b"abc");
+        let abd = int.intern_slice(b"abd");
+        assert_eq!(&*abc, b"abc");
+        let owned = String::from("abc").into_bytes();
+        assert_eq!(int.intern_slice(&owned), abc);
+        assert_ne!(abc, abd);
+        assert_eq!(int.len(), 2);
+
+        // Equal slices hash the same, because they share the pointer
+        let set: std::collections::HashSet<_> = vec![abc, abd, int.intern_slice(b"abc")].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn interner_clear_test() {
+        let mut int = Interner::with_capacity(4);
+        for i in 0..100 {
+            int.intern(i);
+        }
+        assert!(int.state.borrow().holders.len() > 1);
+
+        int.clear();
+        assert!(int.is_empty());
+        assert!(!int.contains(&1));
+        assert_eq!(int.state.borrow().holders.len(), 1);
+        assert_eq!(int.state.borrow().holders[0].items.capacity(), 4);
+
+        // The interner is usable again, with fresh symbols
+        assert_eq!(int.intern_symbol(42), Symbol(0));
+        assert_eq!(*int.intern(42), 42);
+        assert_eq!(int.len(), 1);
+    }
+
+    #[test]
+    fn interner_stats_test() {
+        let int = Interner::with_capacity(4);
+        assert_eq!(int.stats(), InternerStats { holders: 1, capacity: 4, items: 0, item_bytes: 0 });
+
+        for i in 0..10u64 {
+            int.intern(i);
+            int.intern(i);
+        }
+        // Holders of capacity 4 and 6
+        assert_eq!(int.stats(), InternerStats { holders: 2, capacity: 10, items: 10, item_bytes: 80 });
+    }
+
+    #[test]
+    fn interner_custom_hasher_test() {
+        use std::hash::{BuildHasherDefault, Hasher};
+
+        // A fast, non DoS-resistant hasher
+        #[derive(Default)]
+        struct Fnv(u64);
+        impl Hasher for Fnv {
+            fn finish(&self) -> u64 {
+                self.0
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                for &byte in bytes {
+                    self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x100000001b3);
+                }
+            }
+        }
+
+        let int: Interner<String, BuildHasherDefault<Fnv>> = Interner::default();
+        let a = int.intern(String::from("a"));
+        assert_eq!(int.intern(String::from("a")), a);
+        assert_eq!(int.get_interned("a"), Some(a));
+        assert_eq!(int.iter().count(), 1);
+
+        let collected: Interner<char, BuildHasherDefault<Fnv>> = "hello".chars().collect();
+        assert_eq!(collected.len(), 4);
+    }
+
+    #[test]
+    fn interner_into_iter_test() {
+        let int = Interner::with_capacity(2);
+        for word in "the quick brown fox jumps over the lazy dog".split(' ') {
+            int.intern(String::from(word));
+        }
+        assert!(int.state.borrow().holders.len() > 1);
+
+        let mut words: Vec<String> = Vec::new();
+        let into_iter = int.into_iter();
+        assert_eq!(into_iter.len(), 8);
+        words.extend(into_iter);
+        assert_eq!(words, ["the", "quick", "brown", "fox", "jumps", "over", "lazy", "dog"]);
+
+        assert_eq!(Interner::<u8>::new().into_iter().next(), None);
+    }
+
+    #[test]
+    fn interner_reserve_test() {
+        let int = Interner::with_capacity(8);
+        int.intern(0);
+        // There is enough spare room already
+        int.reserve(7);
+        assert_eq!(int.state.borrow().holders.len(), 1);
+
+        int.reserve(100);
+        assert_eq!(int.state.borrow().holders.len(), 2);
+        assert!(int.state.borrow().holders[1].items.capacity() >= 100);
+        for i in 1..101 {
+            int.intern(i);
+        }
+        assert_eq!(int.state.borrow().holders.len(), 2);
+        assert_eq!(int.len(), 101);
+        assert!((0..101).all(|i| int.contains(&i)));
+    }
+
+    #[test]
+    fn interner_builder_test() {
+        let int = InternerBuilder::new()
+            .initial_capacity(2)
+            .growth_factor(3.0)
+            .build();
+        for i in 0..10 {
+            int.intern(i);
+        }
+        let capacities: Vec<usize> = int.state.borrow().holders.iter()
+            .map(|holder| holder.items.capacity())
+            .collect();
+        assert_eq!(capacities, vec![2, 6, 18]);
+        assert_eq!(InternerBuilder::default(), InternerBuilder::new());
+    }
+
+    #[test]
+    fn interner_expected_items_test() {
+        let int = InternerBuilder::new().expected_items(500).build();
+        for i in 0..500 {
+            int.intern(i);
+        }
+        assert_eq!(int.holders_len(), 2);
+        assert_eq!(int.capacity(), 500);
+        // More items than expected are held as usual
+        int.intern(500);
+        assert_eq!(int.holders_len(), 3);
+
+        let int = Interner::new();
+        int.set_expected_items(100);
+        for i in 0..100 {
+            int.intern(i);
+        }
+        assert_eq!(int.holders_len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn interner_builder_growth_factor_test() {
+        InternerBuilder::new().growth_factor(1.0);
+    }
+
+    #[test]
+    fn interner_intern_iter_test() {
+        let int = Interner::new();
+        let interned = int.intern_iter(vec!['a', 'b', 'a', 'c']);
+        assert_eq!(interned.len(), 4);
+        assert_eq!(int.len(), 3);
+        assert_eq!(*interned[1], 'b');
+        assert_eq!(interned[0], interned[2]);
+        assert_eq!(interned[0].as_ptr(), int.intern('a').as_ptr());
+    }
+
+    #[test]
+    fn interner_shrink_to_fit_test() {
+        let mut int = Interner::with_capacity(2);
+        int.intern(1);
+        int.intern(2);
+        int.intern(3);
+        int.reserve(100);
+        assert_eq!(int.stats().holders, 3);
+        let address = int.intern(3).addr();
+        int.shrink_to_fit();
+        assert_eq!(int.stats().holders, 2);
+        assert_eq!(int.intern(3).addr(), address);
+        assert_eq!(int.len(), 3);
+
+        // The first holder is kept even if it's empty
+        int.clear();
+        int.reserve(100);
+        int.shrink_to_fit();
+        assert_eq!(int.stats().holders, 1);
+    }
+
+    #[test]
+    fn interner_clone_test() {
+        let int = Interner::with_capacity(2);
+        let symbols: Vec<Symbol> = (0..5).map(|i| int.intern_symbol(i)).collect();
+        let clone = int.clone();
+        assert_eq!(clone.stats(), int.stats());
+        for (i, &symbol) in symbols.iter().enumerate() {
+            assert_eq!(*clone.resolve(symbol), i);
+            assert_ne!(clone.resolve(symbol), int.resolve(symbol));
+        }
+        // The clone is independent of the original
+        clone.intern(5);
+        assert_eq!(clone.len(), 6);
+        assert_eq!(int.len(), 5);
+        assert!(clone.contains(&4));
+    }
+
+    #[test]
+    fn interner_capacity_test() {
+        let int = Interner::with_capacity(4);
+        assert_eq!(int.capacity(), 4);
+        for i in 0..5 {
+            int.intern(i);
+        }
+        assert_eq!(int.capacity(), 4 + 6);
+        assert_eq!(int.capacity(), int.stats().capacity);
+    }
+
+    #[test]
+    fn interner_intern_entry_test() {
+        let int = Interner::new();
+        let a = int.intern_entry('a');
+        assert!(a.is_vacant());
+        let other_a = int.intern_entry('a');
+        assert_eq!(other_a, InternEntry::Occupied(a.intern()));
+        assert_eq!(*other_a.intern(), 'a');
+
+        // Entries are Copy even if the items aren't
+        let strings = Interner::new();
+        let entry = strings.intern_entry(String::from("a"));
+        let copy = entry;
+        assert_eq!(entry, copy);
+    }
+
+    #[test]
+    fn interner_symbols_test() {
+        let int = Interner::new();
+        let symbols: Vec<Symbol> = ['a', 'b', 'a', 'c'].iter().map(|&c| int.intern_symbol(c)).collect();
+        assert_eq!(int.symbols().collect::<Vec<_>>(), vec![Symbol(0), Symbol(1), Symbol(2)]);
+        assert!(symbols[0] < symbols[1]);
+        assert_eq!(symbols.iter().max(), Some(&Symbol(2)));
+        let set: std::collections::BTreeSet<Symbol> = symbols.into_iter().collect();
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn interner_intern_cow_test() {
+        use std::borrow::Cow;
+
+        let int: Interner<Box<str>> = Interner::new();
+        let hello = int.intern_cow(Cow::Borrowed("hello"));
+        assert_eq!(int.intern_cow(Cow::<str>::Owned(String::from("hello"))), hello);
+        assert_eq!(int.intern_cow(Cow::Borrowed("hello")), hello);
+        assert_eq!(&**hello, "hello");
+        assert_eq!(int.len(), 1);
+
+        // Items which are their own owned form
+        let chars: Interner<char> = Interner::new();
+        let a = chars.intern_cow(Cow::Borrowed(&'a'));
+        assert_eq!(chars.intern_cow(Cow::Owned('a')), a);
+    }
+
+    #[test]
+    fn interner_index_test() {
+        let int = Interner::new();
+        let a = int.intern_symbol('a');
+        let b = int.intern_symbol('b');
+        assert_eq!(int[a], 'a');
+        assert_eq!(&int[b], &*int.resolve(b));
+    }
+
+    #[test]
+    #[should_panic(expected = "symbol 1 is out of range for an interner of 1 items")]
+    fn interner_index_out_of_range_test() {
+        let int = Interner::new();
+        int.intern('a');
+        let _ = int[Symbol(1)];
+    }
+
+    #[test]
+    fn interner_check_invariants_test() {
+        let mut int = Interner::with_capacity(1);
+        for i in 0..100 {
+            int.intern(i % 37);
+        }
+        int.reserve(50);
+        assert_eq!(int.check_invariants(), Ok(()));
+        int.shrink_to_fit();
+        assert_eq!(int.check_invariants(), Ok(()));
+
+        // Break the interner on purpose
+        let duplicate = int.intern_symbol(5);
+        int.state.get_mut().index.values_mut().next().unwrap().push(duplicate);
+        assert!(int.check_invariants().is_err());
+        int.clear();
+        assert_eq!(int.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn interner_filter_to_new_test() {
+        let int: Interner<u32> = (0..10).collect();
+        let even = int.filter_to_new(|item| item % 2 == 0);
+        assert_eq!(even.iter().map(|item| *item).collect::<Vec<_>>(), vec![0, 2, 4, 6, 8]);
+        assert_eq!(*even.resolve(Symbol(1)), 2);
+        assert_ne!(even.intern(4), int.intern(4));
+        assert_eq!(int.len(), 10);
+
+        // The new interner is configured like the original one
+        let boxed = InternerBuilder::new().boxed().linear_threshold(0).expected_items(500).build();
+        for i in 0..100 {
+            boxed.intern(i);
+        }
+        let filtered = boxed.filter_to_new(|item| item % 3 == 0);
+        assert_eq!(filtered.capacity(), 1);
+        let state = filtered.state.borrow();
+        assert!(state.boxed);
+        assert!(state.hashes.is_none());
+        assert_eq!(state.expected_items, 500);
+        drop(state);
+        assert_eq!(filtered.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn interner_intern_static_test() {
+        static KEYWORDS: [&str; 2] = ["fn", "let"];
+
+        let int = Interner::with_capacity(1);
+        let keyword = int.intern_static(&KEYWORDS[0]);
+        assert_eq!(keyword.as_ptr(), &KEYWORDS[0] as *const _);
+        assert_eq!(int.intern("fn"), keyword);
+        int.intern("main");
+        let other = int.intern_static(&KEYWORDS[1]);
+        // Already interned items are not replaced by the static ones
+        assert_eq!(int.intern_static(&"main"), int.intern("main"));
+        assert_eq!(int.len(), 3);
+        assert_eq!(int.state.borrow().holders[0].items.len(), 1);
+        assert_eq!(int.check_invariants(), Ok(()));
+        // SAFETY: the address was produced by this interner
+        assert_eq!(unsafe { int.intern_from_addr(other.addr()) }, other);
+
+        let clone = int.clone();
+        assert_eq!(clone.intern("fn"), keyword);
+        assert_eq!(*clone.resolve(Symbol(1)), "main");
+        assert_eq!(clone.check_invariants(), Ok(()));
+        assert_eq!(int.into_iter().collect::<Vec<_>>(), vec!["main"]);
+    }
+
+    #[test]
+    fn interner_to_vec_test() {
+        let int = Interner::with_capacity(1);
+        for c in "hello".chars() {
+            int.intern(c);
+        }
+        let items = int.to_vec();
+        assert_eq!(items, vec![&'h', &'e', &'l', &'o']);
+        assert_eq!(items[2] as *const char, int.resolve(Symbol(2)).as_ptr());
+    }
+
+    #[test]
+    fn interner_leak_test() {
+        fn keyword() -> Intern<'static, String> {
+            let int = Interner::new();
+            int.intern(String::from("fn"));
+            int.leak().intern(String::from("fn"))
+        }
+
+        assert_eq!(*keyword(), "fn");
+    }
+
+    #[test]
+    fn interner_merge_test() {
+        static STATIC_D: char = 'd';
+
+        let int = Interner::new();
+        int.intern('a');
+        int.intern('b');
+        let other = Interner::new();
+        other.intern('c');
+        other.intern_static(&STATIC_D);
+        other.intern('a');
+        let remap = int.merge(other);
+        assert_eq!(remap, vec![Symbol(2), Symbol(3), Symbol(0)]);
+        assert_eq!(int.len(), 4);
+        assert_eq!(int.intern('d').as_ptr(), &STATIC_D as *const char);
+        assert_eq!(int.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn interner_intern_full_test() {
+        let int = Interner::new();
+        int.intern('a');
+        let (b, symbol) = int.intern_full('b');
+        assert_eq!(*b, 'b');
+        assert_eq!(symbol, Symbol(1));
+        assert_eq!(int.intern_full('b'), (b, symbol));
+    }
+
+    #[test]
+    fn interner_intern_arc_test() {
+        use std::sync::Arc;
+
+        let int = Interner::new();
+        let shared = Arc::new(String::from("shared"));
+        let interned = int.intern_arc(Arc::clone(&shared));
+        assert_eq!(interned.as_ptr(), &*shared as *const String);
+        assert_eq!(Arc::strong_count(&shared), 2);
+        // Equal items are deduplicated, the given reference is dropped
+        assert_eq!(int.intern(String::from("shared")), interned);
+        assert_eq!(int.intern_arc(Arc::new(String::from("shared"))), interned);
+        let owned = int.intern(String::from("owned"));
+        assert_eq!(int.intern_arc(Arc::new(String::from("owned"))), owned);
+        assert_eq!(int.check_invariants(), Ok(()));
+
+        let clone = int.clone();
+        assert_eq!(clone.intern(String::from("shared")).as_ptr(), interned.as_ptr());
+        assert_eq!(Arc::strong_count(&shared), 3);
+        drop(clone);
+        let merged = Interner::new();
+        merged.merge(int);
+        assert_eq!(Arc::strong_count(&shared), 2);
+        drop(merged);
+        assert_eq!(Arc::strong_count(&shared), 1);
+    }
+
+    #[test]
+    fn interner_reserve_exact_test() {
+        let int = Interner::with_capacity(32);
+        for i in 0..30 {
+            int.intern(i);
+        }
+        int.reserve_exact(2);
+        assert_eq!(int.holders_len(), 1);
+        int.reserve_exact(5);
+        assert_eq!(int.holders_len(), 2);
+        assert_eq!(int.capacity(), 32 + 5);
+        for i in 30..40 {
+            int.intern(i);
+        }
+        // The holder after the exact one grows from its capacity
+        assert_eq!(int.holders_len(), 3);
+        assert_eq!(int.capacity(), 32 + 5 + 7);
+        assert_eq!(int.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn interner_check_invariants_growth_test() {
+        let mut int = InternerBuilder::new().initial_capacity(1).build();
+        for i in 0..100 {
+            int.intern(i);
+        }
+        int.reset();
+        int.intern(0);
+        int.reserve(50);
+        for i in 1..100 {
+            int.intern(i);
+        }
+        assert_eq!(int.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn interner_truncate_to_test() {
+        static STATIC_X: char = 'x';
+
+        let mut int = Interner::with_capacity(2);
+        int.intern('a');
+        int.intern_static(&STATIC_X);
+        int.intern('b');
+        let snapshot = int.snapshot();
+        for c in "cdefgh".chars() {
+            int.intern(c);
+        }
+        int.intern_static(&'y');
+        int.truncate_to(snapshot);
+        assert_eq!(int.snapshot(), snapshot);
+        assert_eq!(int.to_vec(), vec![&'a', &'x', &'b']);
+        assert!(!int.contains(&'c'));
+        assert!(!int.contains(&'y'));
+        assert_eq!(int.check_invariants(), Ok(()));
+
+        // The truncated interner works as usual
+        assert_eq!(int.intern_symbol('c'), Symbol(3));
+        assert_eq!(int.intern_symbol('b'), Symbol(2));
+        assert_eq!(int.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn interner_truncate_to_stale_snapshot_test() {
+        // A snapshot taken before a clear, while the items are now held by a reserved holder
         let mut int = Interner::new();
-        let a1 = int.intern('a');
-        let a2 = int.intern('a');
-        let x = int.intern('x');
+        int.intern('a');
+        int.intern('b');
+        let snapshot = int.snapshot();
+        int.clear();
+        int.reserve(100);
+        for c in "cdefg".chars() {
+            int.intern(c);
+        }
+        int.truncate_to(snapshot);
+        assert_eq!(int.to_vec(), vec![&'c', &'d']);
+        assert_eq!(int.check_invariants(), Ok(()));
 
-        // AsRef
-        assert_eq!(a1.as_ref(), &'a');
-        // Borrow
-        assert_eq!(<_ as std::borrow::Borrow<char>>::borrow(&a1), &'a');
-        // Deref
-        assert_eq!(*a1, 'a');
-        // TODO: Debug and Display test
-        // PartialEq
-        assert_eq!(a1, a2);
-        assert_ne!(a1, x);
-        // TODO: Hash test
+        // A snapshot of another interner
+        let other = Interner::new();
+        other.intern(1);
+        let snapshot = other.snapshot();
+        let mut int = Interner::new();
+        int.reserve(100);
+        for i in 10..15 {
+            int.intern(i);
+        }
+        int.truncate_to(snapshot);
+        assert_eq!(int.to_vec(), vec![&10]);
+        assert_eq!(int.check_invariants(), Ok(()));
+        assert_eq!(int.intern_symbol(11), Symbol(1));
     }
 
     #[test]
-    fn interner_iter_test() {
+    fn interner_intern_any_test() {
+        let int: Interner<Vec<u8>> = Interner::new();
+        let bytes = int.intern_any(&[1u8, 2][..]);
+        assert_eq!(int.intern_any(vec![1u8, 2]), bytes);
+        assert_eq!(int.len(), 1);
+    }
+
+    #[test]
+    fn interner_iter_symbols_test() {
+        let int = Interner::new();
+        int.intern('a');
+        int.intern('b');
+        int.intern('a');
+        let pairs: Vec<_> = int.iter_symbols().collect();
+        assert_eq!(pairs.len(), 2);
+        for (symbol, item) in pairs {
+            assert_eq!(int.resolve(symbol), item);
+        }
+        assert_eq!(*int.iter_symbols().nth(1).unwrap().1, 'b');
+    }
+
+    #[test]
+    fn interner_zero_sized_test() {
+        #[derive(PartialEq, Eq, Hash, Debug)]
+        struct Unit;
+
+        let int = Interner::new();
+        let a = int.intern(Unit);
+        for _ in 0..100 {
+            assert_eq!(int.intern(Unit), a);
+        }
+        assert_eq!(int.len(), 1);
+        assert_eq!(int.holders_len(), 1);
+        assert_eq!(int.get_interned(&Unit), Some(a));
+        assert!(int.check_invariants().is_ok());
+
+        let unit_int = Interner::new();
+        assert_eq!(unit_int.intern(()), unit_int.intern(()));
+        assert_eq!(unit_int.len(), 1);
+    }
+
+    #[test]
+    fn interner_deterministic_order_test() {
+        let words = ["delta", "alpha", "charlie", "alpha", "bravo", "delta", "echo"];
+        // Every interner gets a differently seeded hasher
+        let first: Interner<&str> = Interner::new();
+        let second: Interner<&str> = Interner::new();
+        let first_symbols: Vec<_> = words.iter().map(|&word| first.intern_symbol(word)).collect();
+        let second_symbols: Vec<_> = words.iter().map(|&word| second.intern_symbol(word)).collect();
+        assert_eq!(first_symbols, second_symbols);
+        assert_eq!(first.to_vec(), second.to_vec());
+        assert_eq!(first.to_vec(), vec![&"delta", &"alpha", &"charlie", &"bravo", &"echo"]);
+        assert!(first.iter().map(|item| *item).eq(second.iter().map(|item| *item)));
+
+        // Also once the hash index is built, with hashers seeded differently for sure
+        let words: Vec<String> = (0..100).map(|i| (i * 7 % 40).to_string()).collect();
+        let first = Interner::with_hasher(std::collections::hash_map::RandomState::new());
+        let second = InternerBuilder::new()
+            .linear_threshold(0)
+            .build_with_hasher(std::collections::hash_map::RandomState::new());
+        let first_symbols: Vec<_> = words.iter().map(|word| first.intern_symbol(word.clone())).collect();
+        let second_symbols: Vec<_> = words.iter().map(|word| second.intern_symbol(word.clone())).collect();
+        assert!(first.len() > LINEAR_LOOKUP_THRESHOLD);
+        assert_eq!(first_symbols, second_symbols);
+        assert_eq!(first.to_vec(), second.to_vec());
+        assert_eq!(first.to_vec(), words[..40].iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn interner_first_last_test() {
+        let int = Interner::new();
+        assert_eq!(int.first(), None);
+        assert_eq!(int.last(), None);
+        let a = int.intern('a');
+        assert_eq!(int.first(), Some(a));
+        assert_eq!(int.last(), Some(a));
+        let b = int.intern('b');
+        int.intern('a');
+        assert_eq!(int.first(), Some(a));
+        assert_eq!(int.last(), Some(b));
+    }
+
+    #[test]
+    fn interner_on_grow_test() {
+        use std::sync::{Arc, Mutex};
+
+        let mut int = InternerBuilder::new().initial_capacity(2).build();
+        let capacities = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&capacities);
+        int.on_grow(move |capacity| sink.lock().unwrap().push(capacity));
+        for i in 0..10 {
+            int.intern(i);
+        }
+        int.reserve_exact(100);
+        let capacities = capacities.lock().unwrap();
+        assert_eq!(capacities.len(), int.holders_len() - 1);
+        assert_eq!(*capacities.last().unwrap(), 100);
+    }
+
+    #[test]
+    fn interner_get_or_intern_with_test() {
+        let int: Interner<String> = Interner::new();
+        let mut calls = 0;
+        let a = int.get_or_intern_with("a", || {
+            calls += 1;
+            String::from("a")
+        });
+        let other_a = int.get_or_intern_with("a", || {
+            calls += 1;
+            String::from("a")
+        });
+        assert_eq!(a, other_a);
+        assert_eq!(calls, 1);
+
+        // The constructor may intern other items
+        let b = int.get_or_intern_with("b", || {
+            int.intern(String::from("c"));
+            String::from("b")
+        });
+        assert_eq!(*b, "b");
+        assert_eq!(int.len(), 3);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn interner_idempotent_prop(items: Vec<u8>, capacity in 1usize..8) {
+            // Small holders, so that many items land on the holder boundaries
+            let int = InternerBuilder::new().initial_capacity(capacity).build();
+            let interned: Vec<_> = items.iter().map(|&item| int.intern(item)).collect();
+            for (&item, &first) in items.iter().zip(&interned) {
+                proptest::prop_assert_eq!(int.intern(item), first);
+            }
+            proptest::prop_assert!(int.check_invariants().is_ok());
+        }
+
+        #[test]
+        fn interner_dedup_prop(mut items: Vec<u8>, probes: Vec<u8>) {
+            let forward = Interner::new();
+            let backward = Interner::new();
+            for &item in &items {
+                forward.intern(item);
+            }
+            for &item in items.iter().rev() {
+                backward.intern(item);
+            }
+            items.sort_unstable();
+            items.dedup();
+            for int in [&forward, &backward] {
+                let mut held: Vec<u8> = int.to_vec().into_iter().copied().collect();
+                held.sort_unstable();
+                proptest::prop_assert_eq!(&held, &items);
+                for probe in &probes {
+                    proptest::prop_assert_eq!(int.contains(probe), items.binary_search(probe).is_ok());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn interner_into_partition_test() {
+        let int = InternerBuilder::new().initial_capacity(1).build();
+        for word in ["live", "dead", "live", "also live", "dead too"] {
+            int.intern(String::from(word));
+        }
+        let (live, dead) = int.into_partition(|word| word.contains("live"));
+        assert_eq!(live, vec![String::from("live"), String::from("also live")]);
+        assert_eq!(dead, vec![String::from("dead"), String::from("dead too")]);
+    }
+
+    #[test]
+    fn interner_resolve_many_test() {
+        let int = Interner::new();
+        let tokens = ["let", "x", "=", "x"];
+        let symbols: Vec<_> = tokens.iter().map(|&token| int.intern_symbol(token)).collect();
+        let resolved = int.resolve_many(&symbols);
+        assert!(resolved.iter().map(|token| **token).eq(tokens));
+        assert_eq!(resolved[1], resolved[3]);
+        assert!(int.resolve_many(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "symbol 5 is out of range")]
+    fn interner_resolve_many_out_of_range_test() {
+        let int = Interner::new();
+        let a = int.intern_symbol('a');
+        int.resolve_many(&[a, Symbol(5)]);
+    }
+
+    #[test]
+    fn interner_policy_test() {
+        struct Doubling;
+
+        impl InternerPolicy for Doubling {
+            const BASE: usize = 2;
+            const GROWTH: f32 = 2.0;
+        }
+
+        let int: Interner<i32, _, Doubling> = Interner::with_policy();
+        for i in 0..14 {
+            int.intern(i);
+        }
+        // 2 + 4 + 8
+        assert_eq!(int.holders_len(), 3);
+        assert_eq!(int.capacity(), 14);
+        assert_eq!(int.check_invariants(), Ok(()));
+
+        // The policy is kept by the clones, and reserving grows from it as well
+        let clone = int.clone();
+        clone.reserve(1);
+        assert_eq!(clone.capacity(), 14 + 16);
+
+        let default: Interner<i32, _, DefaultPolicy> = Interner::with_policy();
+        assert_eq!(default.capacity(), Interner::<i32>::new().capacity());
+    }
+
+    #[test]
+    fn interner_try_resolve_test() {
+        let int = Interner::new();
+        let a = int.intern_symbol('a');
+        let b = int.intern_symbol('b');
+        assert!(int.is_valid_symbol(a));
+        assert!(int.is_valid_symbol(b));
+        assert!(!int.is_valid_symbol(Symbol(2)));
+        assert_eq!(int.try_resolve(b), Some(int.resolve(b)));
+        assert_eq!(int.try_resolve(Symbol(u32::MAX)), None);
+    }
+
+    #[test]
+    fn interner_extend_interns_test() {
+        let source = Interner::new();
+        let handles: Vec<_> = ["a", "b", "a"].iter().map(|&s| source.intern(String::from(s))).collect();
+        let mut target = Interner::new();
+        target.intern(String::from("b"));
+        target.extend(handles.iter().copied());
+        assert_eq!(target.len(), 2);
+        assert_eq!(*target.to_vec()[1], "a");
+        // The references point into the target, not into the source
+        let a = target.intern(String::from("a"));
+        assert_ne!(a.addr(), handles[0].addr());
+    }
+
+    #[test]
+    fn interner_linear_threshold_test() {
+        let mut int = InternerBuilder::new().linear_threshold(4).build();
+        let first: Vec<_> = (0..3).map(|i| int.intern(i)).collect();
+        // Only a few items are held, so the index isn't built yet
+        assert!(int.state.borrow().index.is_empty());
+        assert_eq!(int.intern(1), first[1]);
+        assert_eq!(int.get_interned(&3), None);
+        assert_eq!(int.check_invariants(), Ok(()));
+
+        int.intern(3);
+        assert_eq!(int.state.borrow().index.len(), 4);
+        assert!(int.state.borrow().hashes.is_none());
+        assert_eq!(int.intern(1), first[1]);
+        assert_eq!(int.check_invariants(), Ok(()));
+
+        // Clearing the interner goes back to the linear scan
+        int.clear();
+        int.intern(0);
+        assert!(int.state.borrow().index.is_empty());
+        assert_eq!(int.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn interner_build_map_test() {
+        let int = Interner::new();
+        for c in "abcab".chars() {
+            int.intern(c);
+        }
+        let map = int.build_map(|symbol, item| {
+            // Interning while building doesn't extend the map
+            int.intern(item.to_ascii_uppercase());
+            (symbol, *item)
+        });
+        assert_eq!(map, vec![(Symbol(0), 'a'), (Symbol(1), 'b'), (Symbol(2), 'c')]);
+        assert_eq!(int.len(), 6);
+    }
+
+    #[test]
+    fn interner_compact_test() {
+        let mut int = InternerBuilder::new().initial_capacity(2).build();
+        let words = ["a", "b", "c", "d", "e", "f", "g"];
+        let mut symbols: Vec<_> = words.iter().map(|&word| int.intern_symbol(String::from(word))).collect();
+        int.intern_static(Box::leak(Box::new(String::from("static"))));
+        symbols.push(int.intern_symbol(String::from("static")));
+        symbols.push(int.intern_symbol(String::from("h")));
+        assert!(int.holders_len() > 1);
+        int.compact();
+        assert_eq!(int.holders_len(), 1);
+        assert_eq!(int.capacity(), 8);
+        assert_eq!(int.check_invariants(), Ok(()));
+        for (symbol, word) in symbols.iter().zip(words.iter().chain(&["static", "h"])) {
+            assert_eq!(*int.resolve(*symbol), *word);
+            assert_eq!(int.intern_symbol(String::from(*word)), *symbol);
+        }
+
+        // A single holder with spare room is shrunk too
         let mut int = Interner::new();
+        int.reserve(100);
+        for i in 0..10 {
+            int.intern(i);
+        }
+        int.compact();
+        assert_eq!(int.capacity(), int.len());
+        let mut int = Interner::new();
+        int.intern('a');
+        int.compact();
+        assert_eq!(int.capacity(), 1);
+        assert_eq!(*int.resolve(Symbol(0)), 'a');
+        assert_eq!(int.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn interner_intern_ref_only_test() {
+        let int = Interner::new();
+        let a: &String = int.intern_ref_only(String::from("a"));
+        let other_a = int.intern_ref_only(String::from("a"));
+        assert!(std::ptr::eq(a, other_a));
+        assert_eq!(a, "a");
+        assert_eq!(int.len(), 1);
+    }
+
+    #[test]
+    fn interner_symbol_from_index_test() {
+        let int = Interner::new();
+        let a = int.intern_symbol('a');
+        assert_eq!(int.symbol_from_index(0), Some(a));
+        assert_eq!(int.symbol_from_index(1), None);
+        assert_eq!(int.symbol_from_index(usize::MAX), None);
+    }
+
+    #[test]
+    fn interner_hits_misses_test() {
+        let int: Interner<String> = Interner::new();
+        for word in ["a", "b", "a", "a"] {
+            int.intern(String::from(word));
+        }
+        int.intern_ref("b");
+        int.get_or_intern_with("c", || String::from("c"));
+        assert!(int.contains("a"));
+        assert_eq!(int.get_interned("d"), None);
+        assert_eq!(int.hits(), 3);
+        assert_eq!(int.misses(), 3);
+        assert_eq!(int.misses(), int.len() as u64);
+    }
+
+    #[test]
+    fn interner_intern_boxed_test() {
+        let mut int = InternerBuilder::new().initial_capacity(1).build();
+        int.intern(String::from("a"));
+        let boxed = Box::new(String::from("b"));
+        let address: *const String = &*boxed;
+        let b = int.intern_boxed(boxed);
+        // The item wasn't moved out of the box
+        assert_eq!(b.as_ptr(), address);
+        assert_eq!(int.intern(String::from("b")), b);
+        assert_eq!(int.intern_boxed(Box::new(String::from("a"))), int.intern(String::from("a")));
+        int.intern(String::from("c"));
+        assert_eq!(int.check_invariants(), Ok(()));
+
+        let cloned = int.clone();
+        assert_ne!(cloned.intern(String::from("b")).as_ptr(), address);
+        assert_eq!(cloned.check_invariants(), Ok(()));
+
+        int.compact();
+        assert_eq!(int.intern(String::from("b")).as_ptr(), address);
+        let items = int.into_iter();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items.collect::<Vec<_>>(), vec![String::from("a"), String::from("b"), String::from("c")]);
+    }
+
+    #[test]
+    fn interner_new_boxed_test() {
+        let mut int: Interner<String> = Interner::new_boxed();
+        let a = int.intern(String::from("a")).as_ptr();
+        let b = int.intern_ref("b").as_ptr();
         for i in 0..100 {
+            int.intern(i.to_string());
+        }
+        assert_eq!(int.intern(String::from("a")).as_ptr(), a);
+        assert_eq!(int.intern(String::from("b")).as_ptr(), b);
+        assert_eq!(int.len(), 102);
+        // No item is held by the holders
+        assert_eq!(int.holders_len(), 1);
+        assert_eq!(int.capacity(), 1);
+        assert_eq!(int.check_invariants(), Ok(()));
+
+        int.clear();
+        int.intern(String::from("c"));
+        assert_eq!(int.capacity(), 1);
+        assert_eq!(int.into_iter().collect::<Vec<_>>(), vec![String::from("c")]);
+    }
+
+    #[test]
+    fn interner_intern_reporting_test() {
+        let int = Interner::new();
+        int.intern('x');
+        let report = int.intern_reporting("xyzyx".chars());
+        let duplicates = report.iter().filter(|&&(_, fresh)| !fresh).count();
+        assert_eq!(duplicates, 3);
+        assert_eq!(report[1].0, report[3].0);
+        assert!(report[1].1 && !report[3].1);
+        assert!(int.intern_reporting(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn interner_reset_test() {
+        use std::sync::{Arc, Mutex};
+
+        let mut int = InternerBuilder::new().initial_capacity(2).build();
+        let allocations = Arc::new(Mutex::new(0));
+        let counter = Arc::clone(&allocations);
+        int.on_grow(move |_| *counter.lock().unwrap() += 1);
+        for i in 0..20 {
             int.intern(i);
         }
+        let holders = int.holders_len();
+        let capacity = int.capacity();
+        let allocated = *allocations.lock().unwrap();
 
-        let collected: Vec<i32> = int.iter().map(|i| *i).collect();
+        int.reset();
+        assert!(int.is_empty());
+        assert_eq!(int.holders_len(), 1);
+        assert_eq!(int.check_invariants(), Ok(()));
+        int.intern(100);
+        assert_eq!(int.get_interned(&0), None);
+        // Reset in the middle of the reuse, the holders must keep their order
+        for i in 0..5 {
+            int.intern(i);
+        }
+        int.reset();
+        for i in 0..20 {
+            int.intern(i * 3);
+        }
+        assert_eq!(int.holders_len(), holders);
+        assert_eq!(int.capacity(), capacity);
+        assert_eq!(*allocations.lock().unwrap(), allocated);
+        assert_eq!(int.check_invariants(), Ok(()));
+        assert_eq!(*int.resolve(Symbol(19)), 57);
+    }
 
-        assert_eq!(
-            collected,
-            (0..100).collect::<Vec<i32>>()
-        );
+    #[test]
+    fn interner_get_all_test() {
+        let int = Interner::new();
+        let words: Vec<_> = (0..40).map(|i| int.intern(i.to_string())).collect();
+        let queries = [String::from("3"), String::from("missing"), String::from("39")];
+        assert_eq!(int.get_all(&queries), vec![Some(words[3]), None, Some(words[39])]);
+        assert_eq!(int.get_all(vec!["0", "40"]), vec![Some(words[0]), None]);
+        assert!(int.get_all(Vec::<&str>::new()).is_empty());
+        // Looking up doesn't intern anything
+        assert_eq!(int.len(), 40);
+        assert_eq!(int.misses(), 40);
+    }
+
+    #[test]
+    fn interner_hash_index_test() {
+        let int = Interner::new();
+        // Intern enough items to span several holders
+        let first: Vec<_> = (0..1000).map(|i| int.intern(i)).collect();
+        assert!(int.state.borrow().holders.len() > 1);
+        // Interning them again must hit the index
+        for (i, reference) in first.iter().enumerate() {
+            assert_eq!(int.intern(i as i32), *reference);
+        }
+        assert_eq!(int.len(), 1000);
+        assert_eq!(int.state.borrow().index.values().map(|b| b.len()).sum::<usize>(), 1000);
+    }
+
+    #[test]
+    fn interner_hash_collision_test() {
+        // All values of this type have the same hash
+        #[derive(PartialEq, Eq, Debug)]
+        struct Collide(u32);
+        impl std::hash::Hash for Collide {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                0u32.hash(state)
+            }
+        }
+
+        let int = InternerBuilder::new().linear_threshold(0).build();
+        let a = int.intern(Collide(1));
+        let b = int.intern(Collide(2));
+        assert_ne!(a, b);
+        assert_eq!(int.intern(Collide(1)), a);
+        assert_eq!(int.intern(Collide(2)), b);
+        assert_eq!(int.state.borrow().index.len(), 1);
+        assert!(int.contains(&Collide(2)));
+        assert!(!int.contains(&Collide(3)));
+        assert_eq!(int.max_bucket_len(), 2);
+    }
+
+    #[test]
+    fn interner_max_bucket_len_test() {
+        let int = Interner::new();
+        assert_eq!(int.max_bucket_len(), 0);
+        for i in 0..100 {
+            int.intern(i);
+        }
+        assert_eq!(int.max_bucket_len(), 1);
+    }
+
+    #[test]
+    fn interner_get_interned_test() {
+        let int = Interner::new();
+        let hello = int.intern(String::from("hello"));
+        int.intern(String::from("world"));
+
+        // Look up by a borrowed form
+        assert_eq!(int.get_interned("hello"), Some(hello));
+        assert!(int.get_interned("world").is_some());
+        assert_eq!(int.get_interned("missing"), None);
+        // Nothing was interned by the lookups
+        assert_eq!(int.state.borrow().holders[0].items.len(), 2);
+    }
+
+    #[test]
+    fn interner_try_get_test() {
+        let int = Interner::new();
+        int.intern('a');
+        // Lookups work while the interner is being iterated over
+        for item in &int {
+            assert_eq!(int.try_get(&*item), Some(item));
+        }
+        assert_eq!(int.try_get(&'b'), None);
+        assert_eq!(int.len(), 1);
     }
 }
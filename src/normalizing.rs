@@ -0,0 +1,82 @@
+use core::hash::{BuildHasher, Hash};
+
+use crate::{DefaultHashBuilder, Intern, KeyedInterner};
+
+/// An interner which deduplicates the items by a normalized form of them.
+///
+/// The normalization function turns an item into a key, and items with equal keys
+/// are considered the same item. The first seen item is the one which is held:
+///
+/// ```
+/// use libintern::NormalizingInterner;
+///
+/// let interner = NormalizingInterner::new(|name: &String| name.to_lowercase());
+/// let foo = interner.intern(String::from("Foo"));
+/// assert_eq!(interner.intern(String::from("foo")), foo);
+/// assert_eq!(*foo, "Foo");
+/// ```
+pub struct NormalizingInterner<'a, T: 'a, K, F, S = DefaultHashBuilder> {
+    items: KeyedInterner<'a, K, T, S>,
+    normalize: F
+}
+
+impl<'a, T: 'a, K, F: Fn(&T) -> K> NormalizingInterner<'a, T, K, F> {
+    /// Create an interner which normalizes the items with the given function.
+    pub fn new(normalize: F) -> Self {
+        Self::with_hasher(normalize, DefaultHashBuilder::default())
+    }
+}
+
+impl<'a, T: 'a, K, F: Fn(&T) -> K, S> NormalizingInterner<'a, T, K, F, S> {
+    /// Create an interner which normalizes the items with the given function
+    /// and uses the given hasher to hash the normalized forms.
+    pub fn with_hasher(normalize: F, hash_builder: S) -> Self {
+        Self { items: KeyedInterner::with_hasher(hash_builder), normalize }
+    }
+
+    /// The number of distinct items held by this interner.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if no items are held by this interner.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<'a, T: 'a, K: Eq + Hash, F: Fn(&T) -> K, S: BuildHasher> NormalizingInterner<'a, T, K, F, S> {
+    /// Intern an item.
+    ///
+    /// This item is dropped if an item with an equal normalized form is already interned,
+    /// in which case a reference to the already interned item is returned instead.
+    pub fn intern(&self, item: T) -> Intern<'_, T> {
+        let key = (self.normalize)(&item);
+        self.items.intern_by_key(item, key)
+    }
+
+    /// Get a reference to the interned item with the same normalized form
+    /// as the given item, without interning it.
+    pub fn get_interned(&self, item: &T) -> Option<Intern<'_, T>> {
+        self.items.get_by_key(&(self.normalize)(item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NormalizingInterner;
+
+    #[test]
+    fn normalizing_interner_test() {
+        let int = NormalizingInterner::new(|item: &String| item.to_lowercase());
+        let foo = int.intern(String::from("Foo"));
+        let other_foo = int.intern(String::from("FOO"));
+        let bar = int.intern(String::from("bar"));
+        assert_eq!(foo, other_foo);
+        assert_ne!(foo, bar);
+        assert_eq!(*other_foo, "Foo");
+        assert_eq!(int.len(), 2);
+        assert_eq!(int.get_interned(&String::from("foo")), Some(foo));
+        assert_eq!(int.get_interned(&String::from("baz")), None);
+    }
+}
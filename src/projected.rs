@@ -0,0 +1,154 @@
+use core::{hash::{BuildHasher, Hash}, ops::Deref};
+
+use crate::{DefaultHashBuilder, Intern, KeyedInterner};
+
+/// An item which is interned by a projection of it.
+///
+/// Items with equal keys are considered the same item by a [`ProjectedInterner`],
+/// e.g. items whose identity is only a subset of their fields.
+/// An item which is its own key can be wrapped in [`Identity`].
+///
+/// ```
+/// use libintern::{InternKey, ProjectedInterner};
+///
+/// #[derive(Clone, PartialEq, Eq, Hash)]
+/// struct Token { text: String, span: (usize, usize) }
+///
+/// impl InternKey for Token {
+///     type Key = String;
+///
+///     fn key(&self) -> String {
+///         self.text.clone()
+///     }
+/// }
+///
+/// let interner = ProjectedInterner::new();
+/// let first = interner.intern(Token { text: String::from("fn"), span: (0, 2) });
+/// let second = interner.intern(Token { text: String::from("fn"), span: (10, 12) });
+/// assert!(first == second);
+/// assert_eq!(second.span, (0, 2));
+/// ```
+pub trait InternKey {
+    /// The projection of the item the items are deduplicated by.
+    type Key: Hash + Eq;
+
+    /// Project the item to its key.
+    fn key(&self) -> Self::Key;
+}
+
+/// An item which is its own [`InternKey`].
+///
+/// ```
+/// use libintern::{Identity, ProjectedInterner};
+///
+/// let interner = ProjectedInterner::new();
+/// let a = interner.intern(Identity(String::from("a")));
+/// assert!(interner.get_by_key(&String::from("a")) == Some(a));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Identity<T>(pub T);
+
+impl<T: Hash + Eq + Clone> InternKey for Identity<T> {
+    type Key = T;
+
+    fn key(&self) -> T {
+        self.0.clone()
+    }
+}
+
+impl<T> Deref for Identity<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// An interner which deduplicates the items by their [`InternKey`].
+///
+/// The full items are held, the first seen item with a given key is the one which is kept.
+pub struct ProjectedInterner<'a, T: 'a + InternKey, S = DefaultHashBuilder> {
+    items: KeyedInterner<'a, T::Key, T, S>
+}
+
+impl<'a, T: 'a + InternKey> ProjectedInterner<'a, T> {
+    pub fn new() -> Self {
+        Self::with_hasher(DefaultHashBuilder::default())
+    }
+}
+
+impl<'a, T: 'a + InternKey, S> ProjectedInterner<'a, T, S> {
+    /// Create an interner which uses the given hasher to hash the keys.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self { items: KeyedInterner::with_hasher(hash_builder) }
+    }
+
+    /// The number of distinct items held by this interner.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if no items are held by this interner.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<'a, T: 'a + InternKey, S: BuildHasher> ProjectedInterner<'a, T, S> {
+    /// Intern an item.
+    ///
+    /// This item is dropped if an item with an equal key is already interned,
+    /// in which case a reference to the already interned item is returned instead.
+    pub fn intern(&self, item: T) -> Intern<'_, T> {
+        let key = item.key();
+        self.items.intern_by_key(item, key)
+    }
+
+    /// Get a reference to the interned item with the given key, without interning anything.
+    pub fn get_by_key(&self, key: &T::Key) -> Option<Intern<'_, T>> {
+        self.items.get_by_key(key)
+    }
+}
+
+impl<'a, T: 'a + InternKey, S: Default> Default for ProjectedInterner<'a, T, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Identity, InternKey, ProjectedInterner};
+
+    #[test]
+    fn projected_interner_test() {
+        struct Token {
+            text: &'static str,
+            line: usize
+        }
+
+        impl InternKey for Token {
+            type Key = &'static str;
+
+            fn key(&self) -> &'static str {
+                self.text
+            }
+        }
+
+        let int = ProjectedInterner::new();
+        let first = int.intern(Token { text: "let", line: 1 });
+        let other = int.intern(Token { text: "let", line: 7 });
+        let name = int.intern(Token { text: "x", line: 1 });
+        assert!(first == other);
+        assert!(first != name);
+        assert_eq!(other.line, 1);
+        assert_eq!(int.len(), 2);
+        assert!(int.get_by_key(&"x") == Some(name));
+
+        // Items which are their own keys
+        let plain = ProjectedInterner::new();
+        assert!(plain.intern(Identity(String::from("a"))) == plain.intern(Identity(String::from("a"))));
+        assert_eq!(**plain.get_by_key(&String::from("a")).unwrap(), "a");
+        assert_eq!(plain.len(), 1);
+    }
+}
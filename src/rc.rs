@@ -0,0 +1,129 @@
+use alloc::{rc::Rc, vec::Vec};
+use core::{borrow::Borrow, cell::RefCell, hash::{BuildHasher, Hash}};
+
+use crate::{DefaultHashBuilder, Intern, Interner};
+
+/// An interner which can also intern items shared by an `Rc`, without copying them.
+///
+/// An [`Interner`] can be sent to another thread, so it can't keep an `Rc` alive,
+/// see [`Interner::intern_arc`]. This interner keeps the interned `Rc`s next to
+/// an inner interner instead, so it can't be sent to another thread itself:
+///
+/// ```
+/// use std::rc::Rc;
+/// use libintern::RcInterner;
+///
+/// let interner = RcInterner::new();
+/// let shared = Rc::new(String::from("a"));
+/// let a = interner.intern_rc(Rc::clone(&shared));
+/// assert!(std::ptr::eq(&*a, &*shared));
+/// assert_eq!(interner.intern(String::from("a")), a);
+/// ```
+///
+/// ```compile_fail
+/// use libintern::RcInterner;
+///
+/// let interner: RcInterner<String> = RcInterner::new();
+/// std::thread::spawn(move || interner.len());
+/// ```
+pub struct RcInterner<'a, T: 'a + Eq, S = DefaultHashBuilder> {
+    items: Interner<'a, T, S>,
+    /// The interned `Rc`s, which keep alive the items the inner interner refers to
+    shared: RefCell<Vec<Rc<T>>>
+}
+
+impl<'a, T: 'a + Eq> RcInterner<'a, T> {
+    pub fn new() -> Self {
+        Self::with_hasher(DefaultHashBuilder::default())
+    }
+}
+
+impl<'a, T: 'a + Eq, S> RcInterner<'a, T, S> {
+    /// Create an interner which uses the given hasher to hash the items.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self { items: Interner::with_hasher(hash_builder), shared: RefCell::new(Vec::new()) }
+    }
+
+    /// The number of distinct items held by this interner.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if no items are held by this interner.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<'a, T: 'a + Eq + Hash, S: BuildHasher> RcInterner<'a, T, S> {
+    /// Intern an item, see [`Interner::intern`].
+    pub fn intern(&self, item: T) -> Intern<'_, T> {
+        self.items.intern(item)
+    }
+
+    /// Intern a shared item without copying it.
+    ///
+    /// If no equal item is interned yet, the interner keeps the `Rc` alive
+    /// and refers to the item inside it. Otherwise, the `Rc` is dropped
+    /// and a reference to the already interned item is returned.
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` distinct items would be interned.
+    pub fn intern_rc(&self, item: Rc<T>) -> Intern<'_, T> {
+        if let Some(interned) = self.items.get_interned(&*item) {
+            return interned
+        }
+        // SAFETY: the `Rc` is kept until this interner is dropped, and the inner interner
+        // is never handed out, so it can't be cloned nor outlive this one
+        let symbol = unsafe { self.items.intern_static_symbol(&*item) };
+        self.shared.borrow_mut().push(item);
+        self.items.resolve(symbol)
+    }
+
+    /// Get a reference to an already interned item, without interning it.
+    ///
+    /// The item can be given by any borrowed form of it.
+    pub fn get_interned<Q>(&self, item: &Q) -> Option<Intern<'_, T>>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash
+    {
+        self.items.get_interned(item)
+    }
+}
+
+impl<'a, T: 'a + Eq, S: Default> Default for RcInterner<'a, T, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::RcInterner;
+
+    #[test]
+    fn rc_interner_test() {
+        let int = RcInterner::new();
+        let shared = Rc::new(String::from("a"));
+        let a = int.intern_rc(Rc::clone(&shared));
+        assert!(std::ptr::eq(&*a, &*shared));
+        assert_eq!(Rc::strong_count(&shared), 2);
+
+        // An equal item drops the given Rc
+        let other = Rc::new(String::from("a"));
+        assert_eq!(int.intern_rc(Rc::clone(&other)), a);
+        assert_eq!(Rc::strong_count(&other), 1);
+        assert_eq!(int.intern(String::from("a")), a);
+
+        // The item outlives the other clones of the Rc
+        drop(shared);
+        assert_eq!(*a, "a");
+        let b = int.intern(String::from("b"));
+        assert_eq!(int.intern_rc(Rc::new(String::from("b"))), b);
+        assert_eq!(int.get_interned("b"), Some(b));
+        assert_eq!(int.len(), 2);
+    }
+}
@@ -0,0 +1,83 @@
+use core::{fmt, hash::{BuildHasher, Hash}, marker::PhantomData};
+
+use serde::{de::{self, SeqAccess, Visitor}, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Interner, InternerPolicy};
+
+/// Serializes the interner as a sequence of the distinct items, in insertion order.
+impl<'a, T: 'a + Eq + Serialize, S, P: InternerPolicy> Serialize for Interner<'a, T, S, P> {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        serializer.collect_seq(self.iter().map(|item| item.0.get_ref()))
+    }
+}
+
+/// Deserializes the interner by interning the items of a sequence, in order.
+///
+/// Because the order is kept, the symbols of the deserialized interner
+/// are the same as the symbols of the serialized one.
+/// A sequence containing equal items is rejected, as it could not have been
+/// produced by an interner and the symbols would not match.
+impl<'de, 'a, T, S, P> Deserialize<'de> for Interner<'a, T, S, P>
+where
+    T: 'a + Eq + Hash + Deserialize<'de>,
+    S: BuildHasher + Default,
+    P: InternerPolicy
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(InternerVisitor(PhantomData))
+    }
+}
+
+struct InternerVisitor<'a, T: 'a + Eq, S, P>(PhantomData<Interner<'a, T, S, P>>);
+
+impl<'de, 'a, T, S, P> Visitor<'de> for InternerVisitor<'a, T, S, P>
+where
+    T: 'a + Eq + Hash + Deserialize<'de>,
+    S: BuildHasher + Default,
+    P: InternerPolicy
+{
+    type Value = Interner<'a, T, S, P>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of distinct items")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let interner = Interner::default();
+        while let Some(item) = seq.next_element()? {
+            let len = interner.len();
+            interner.intern(item);
+            if interner.len() == len {
+                return Err(de::Error::custom("duplicate item in interner"))
+            }
+        }
+        Ok(interner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Interner;
+
+    #[test]
+    fn serde_round_trip_test() {
+        let int = Interner::new();
+        let symbols: Vec<_> = ["c", "a", "b", "a"].iter().map(|s| int.intern_symbol(s.to_string())).collect();
+
+        let json = serde_json::to_string(&int).unwrap();
+        assert_eq!(json, r#"["c","a","b"]"#);
+
+        let back: Interner<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.len(), 3);
+        for (symbol, s) in symbols.iter().zip(["c", "a", "b", "a"].iter()) {
+            assert_eq!(back.resolve(*symbol).as_str(), *s);
+            assert_eq!(back.intern_symbol(s.to_string()), *symbol);
+        }
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+    }
+
+    #[test]
+    fn serde_duplicate_test() {
+        assert!(serde_json::from_str::<Interner<String>>(r#"["a","b","a"]"#).is_err());
+    }
+}
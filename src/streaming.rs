@@ -0,0 +1,160 @@
+use core::{cell::Cell, hash::{BuildHasher, Hash}};
+
+use crate::{DefaultHashBuilder, Intern, Interner};
+
+/// An interner which is fed items one by one, e.g. the tokens of a parsed stream.
+///
+/// The items are fed in batches: [`flush`](Self::flush) ends the current batch
+/// and tells how many items were fed and how many of them were new.
+/// Like with [`Interner`], the references borrow the interner,
+/// so they stay valid while the interner is held across `.await` points:
+///
+/// ```
+/// use std::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+/// use libintern::{Intern, StreamingInterner};
+///
+/// async fn tokenize<'i>(interner: &'i StreamingInterner<'i, String>, chunks: &[&str]) -> Vec<Intern<'i, String>> {
+///     let mut tokens = Vec::new();
+///     for chunk in chunks {
+///         tokens.extend(chunk.split_whitespace().map(|word| interner.feed(String::from(word))));
+///         // The tokens are held while the next chunk is awaited
+///         std::future::ready(()).await;
+///     }
+///     tokens
+/// }
+///
+/// // A minimal executor, the futures above are always ready
+/// fn block_on<F: Future>(future: F) -> F::Output {
+///     let mut context = Context::from_waker(Waker::noop());
+///     match pin!(future).poll(&mut context) {
+///         Poll::Ready(output) => output,
+///         Poll::Pending => unreachable!()
+///     }
+/// }
+///
+/// let interner = StreamingInterner::new();
+/// let tokens = block_on(tokenize(&interner, &["let x", "= x"]));
+/// assert_eq!(tokens[1], tokens[3]);
+/// let batch = interner.flush();
+/// assert_eq!((batch.items, batch.new_items), (4, 3));
+/// ```
+pub struct StreamingInterner<'a, T: 'a + Eq, S = DefaultHashBuilder> {
+    items: Interner<'a, T, S>,
+    /// The hits and the misses of the interner when the current batch started
+    batch_start: Cell<(u64, u64)>
+}
+
+/// Statistics about a batch of items fed to a [`StreamingInterner`], see [`StreamingInterner::flush`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BatchStats {
+    /// The number of items fed in the batch
+    pub items: u64,
+    /// The number of the fed items which weren't interned before
+    pub new_items: u64
+}
+
+impl<'a, T: 'a + Eq> StreamingInterner<'a, T> {
+    pub fn new() -> Self {
+        Self::from_interner(Interner::new())
+    }
+}
+
+impl<'a, T: 'a + Eq, S> StreamingInterner<'a, T, S> {
+    /// Feed the items into the given interner.
+    ///
+    /// The items already held by the interner don't count as new ones.
+    pub fn from_interner(items: Interner<'a, T, S>) -> Self {
+        let batch_start = Cell::new((items.hits(), items.misses()));
+        Self { items, batch_start }
+    }
+
+    /// End the current batch and get the statistics about it.
+    pub fn flush(&self) -> BatchStats {
+        let (start_hits, start_misses) = self.batch_start.get();
+        let (hits, misses) = (self.items.hits(), self.items.misses());
+        self.batch_start.set((hits, misses));
+        let new_items = misses - start_misses;
+        BatchStats { items: hits - start_hits + new_items, new_items }
+    }
+
+    /// The interner which holds the fed items.
+    pub fn as_interner(&self) -> &Interner<'a, T, S> {
+        &self.items
+    }
+
+    /// Stop feeding the items, giving the interner which holds them back.
+    pub fn into_interner(self) -> Interner<'a, T, S> {
+        self.items
+    }
+}
+
+impl<'a, T: 'a + Eq + Hash, S: BuildHasher> StreamingInterner<'a, T, S> {
+    /// Feed an item to the interner, interning it.
+    pub fn feed(&self, item: T) -> Intern<'_, T> {
+        self.items.intern(item)
+    }
+}
+
+impl<'a, T: 'a + Eq, S: Default> Default for StreamingInterner<'a, T, S> {
+    fn default() -> Self {
+        Self::from_interner(Interner::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+
+    use super::{BatchStats, StreamingInterner};
+
+    /// A future which is pending once, like waiting for the next chunk of a stream.
+    struct NextChunk(bool);
+
+    impl Future for NextChunk {
+        type Output = ();
+
+        fn poll(mut self: core::pin::Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                Poll::Pending
+            }
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut context = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output
+            }
+        }
+    }
+
+    #[test]
+    fn streaming_interner_test() {
+        let int = StreamingInterner::new();
+        let chunks = [vec!["let", "x"], vec!["=", "x"]];
+        let tokens = block_on(async {
+            let mut tokens = Vec::new();
+            for chunk in &chunks {
+                for &word in chunk {
+                    tokens.push(int.feed(String::from(word)));
+                }
+                // The references are held across the await
+                NextChunk(false).await;
+            }
+            tokens
+        });
+        assert_eq!(tokens[1], tokens[3]);
+        assert_eq!(*tokens[2], "=");
+        assert_eq!(int.flush(), BatchStats { items: 4, new_items: 3 });
+
+        int.feed(String::from("x"));
+        assert_eq!(int.flush(), BatchStats { items: 1, new_items: 0 });
+        assert_eq!(int.flush(), BatchStats { items: 0, new_items: 0 });
+        assert_eq!(int.into_interner().len(), 3);
+    }
+}
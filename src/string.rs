@@ -0,0 +1,121 @@
+use alloc::{boxed::Box, string::String};
+use core::hash::BuildHasher;
+
+use crate::{DefaultHashBuilder, Intern, Interner, Symbol};
+
+/// An interner of strings.
+///
+/// A thin wrapper around an [`Interner`] of boxed strings,
+/// which interns string slices directly and hands out references to `str`:
+///
+/// ```
+/// use libintern::StringInterner;
+///
+/// let interner = StringInterner::new();
+/// let hello = interner.intern("hello");
+/// assert_eq!(interner.intern(&String::from("hello")), hello);
+/// let symbol = interner.intern_symbol("world");
+/// assert_eq!(interner.resolve(symbol), "world");
+/// ```
+pub struct StringInterner<'a, S = DefaultHashBuilder> {
+    strings: Interner<'a, Box<str>, S>
+}
+
+impl<'a> StringInterner<'a> {
+    pub fn new() -> Self {
+        Self { strings: Interner::new() }
+    }
+}
+
+impl<'a, S> StringInterner<'a, S> {
+    /// Create an interner which uses the given hasher to hash the strings.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self { strings: Interner::with_hasher(hash_builder) }
+    }
+
+    /// The number of distinct strings held by this interner.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns true if no strings are held by this interner.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Get the interned string with the given symbol.
+    ///
+    /// # Panics
+    /// Panics if the symbol was not produced by this interner.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.strings[symbol]
+    }
+
+    /// The interner of boxed strings this interner wraps.
+    pub fn as_interner(&self) -> &Interner<'a, Box<str>, S> {
+        &self.strings
+    }
+}
+
+impl<'a, S: BuildHasher> StringInterner<'a, S> {
+    /// Intern a string slice.
+    ///
+    /// The string is only copied into the interner if no equal string is interned yet.
+    pub fn intern(&self, s: &str) -> Intern<'_, str> {
+        self.strings.intern_str(s)
+    }
+
+    /// Intern an owned string.
+    ///
+    /// If no equal string is interned yet, the string itself is interned instead of a copy of it.
+    pub fn intern_string(&self, s: String) -> Intern<'_, str> {
+        Self::unbox(self.strings.intern(s.into_boxed_str()))
+    }
+
+    /// Intern a string slice and return its [`Symbol`].
+    pub fn intern_symbol(&self, s: &str) -> Symbol {
+        let hash = self.strings.hash_builder.hash_one(s);
+        let mut state = self.strings.state.borrow_mut();
+        match state.find_interned(hash, s) {
+            Some(symbol) => symbol,
+            // The hash of the boxed string is the same as the hash of the slice
+            None => state.hold_new_symbol(hash, Box::from(s))
+        }
+    }
+
+    /// Get a reference to an already interned string, without interning it.
+    pub fn get(&self, s: &str) -> Option<Intern<'_, str>> {
+        self.strings.get_interned(s).map(Self::unbox)
+    }
+
+    fn unbox(boxed: Intern<'_, Box<str>>) -> Intern<'_, str> {
+        // SAFETY: the boxed string is never mutated nor moved,
+        // therefore neither are the bytes it points to
+        unsafe { Intern::from_raw(&**boxed.0.get_ref()) }
+    }
+}
+
+impl<'a, S: Default> Default for StringInterner<'a, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StringInterner;
+
+    #[test]
+    fn string_interner_test() {
+        let int = StringInterner::new();
+        let hello = int.intern("hello");
+        assert_eq!(int.intern_string(String::from("hello")), hello);
+        let world = int.intern_string(String::from("world"));
+        assert_eq!(int.intern("world"), world);
+        assert_eq!(int.get("hello"), Some(hello));
+        assert_eq!(int.get("missing"), None);
+        let symbol = int.intern_symbol("hello");
+        assert_eq!(int.resolve(symbol), "hello");
+        assert_eq!(int.len(), 2);
+    }
+}
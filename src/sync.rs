@@ -0,0 +1,122 @@
+use std::{hash::{BuildHasher, Hash}, sync::Mutex};
+
+use crate::{DefaultHashBuilder, Intern, Interner};
+
+/// The number of shards of a [`SyncInterner`]
+const SYNC_INTERNER_SHARDS: usize = 16;
+
+/// A thread-safe interner.
+///
+/// Unlike [`Interner`], this interner can be shared between threads
+/// and items can be interned through a shared reference.
+/// The items are split between several shards by their hash,
+/// each shard being an [`Interner`] behind its own lock,
+/// so threads interning different items rarely wait for each other.
+///
+/// Equal items always land in the same shard, so interning equal items
+/// from different threads still yields equal references.
+/// The shards share the hasher, so an item is only hashed once,
+/// both to pick its shard and to look it up in the shard.
+pub struct SyncInterner<'a, T: 'a + Eq + Hash, S = DefaultHashBuilder> {
+    shards: Box<[Mutex<Interner<'a, T, S>>]>,
+    /// The hasher used to pick the shard of an item, a copy of the hasher of every shard
+    hash_builder: S
+}
+
+// SAFETY: Interning from several threads hands out shared references
+// to the items to all of those threads, so the items must be Sync
+// in addition to being Send
+unsafe impl<'a, T: 'a + Eq + Hash + Send + Sync, S: Send + Sync> Sync for SyncInterner<'a, T, S> {}
+
+impl<'a, T: 'a + Eq + Hash> SyncInterner<'a, T> {
+    pub fn new() -> Self {
+        Self::with_hasher(DefaultHashBuilder::default())
+    }
+}
+
+impl<'a, T: 'a + Eq + Hash, S: Clone> SyncInterner<'a, T, S> {
+    /// Create an interner which uses the given hasher to hash the items.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            shards: (0..SYNC_INTERNER_SHARDS).map(|_| Mutex::new(Interner::with_hasher(hash_builder.clone()))).collect(),
+            hash_builder
+        }
+    }
+}
+
+impl<'a, T: 'a + Eq + Hash, S: BuildHasher> SyncInterner<'a, T, S> {
+    /// Intern an item.
+    ///
+    /// Works like [`Interner::intern`], except that it only needs a shared reference
+    /// and the returned reference is bound to the borrow of this interner.
+    pub fn intern(&self, item: T) -> Intern<'_, T> {
+        let hash = self.hash_builder.hash_one(&item);
+        // The low bits of the hash pick the bucket in the index of the shard
+        // and the high bits are used by the index too, so the shard is picked by the middle ones
+        let shard = self.shards[((hash >> 32) as usize) % self.shards.len()].lock().unwrap();
+        let (symbol, _) = shard.get_or_intern_hashed_symbol(hash, item);
+        let reference = shard.held_item(symbol);
+        drop(shard);
+        // SAFETY: The shard never mutates nor moves the items it holds, so the reference
+        // stays valid after the lock is released, for as long as this interner is borrowed
+        unsafe { Intern::from_raw(reference) }
+    }
+}
+
+impl<'a, T: 'a + Eq + Hash, S> SyncInterner<'a, T, S> {
+    /// The number of distinct items held by this interner.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.lock().unwrap().len()).sum()
+    }
+
+    /// Returns true if no items are held by this interner.
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| shard.lock().unwrap().is_empty())
+    }
+}
+
+impl<'a, T: 'a + Eq + Hash, S: Clone + Default> Default for SyncInterner<'a, T, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SyncInterner;
+
+    #[test]
+    fn sync_interner_test() {
+        let int = SyncInterner::new();
+        let a = int.intern(String::from("a"));
+        assert_eq!(int.intern(String::from("a")), a);
+        assert_ne!(int.intern(String::from("b")), a);
+        assert_eq!(int.len(), 2);
+
+        // The shards look the items up by the hash of the shared hasher
+        let int = SyncInterner::with_hasher(std::collections::hash_map::RandomState::new());
+        let a = int.intern(String::from("a"));
+        assert_eq!(int.intern(String::from("a")), a);
+        assert!(int.shards.iter().all(|shard| shard.lock().unwrap().check_invariants().is_ok()));
+    }
+
+    #[test]
+    fn sync_interner_threads_test() {
+        let int = SyncInterner::new();
+        let addresses: Vec<Vec<usize>> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..4).map(|_| scope.spawn(|| {
+                (0..500)
+                    .map(|i| int.intern(i.to_string()))
+                    .map(|item| item.as_ref() as *const String as usize)
+                    .collect()
+            })).collect();
+            workers.into_iter().map(|worker| worker.join().unwrap()).collect()
+        });
+        // All threads have seen the very same items
+        assert!(addresses.iter().all(|other| other == &addresses[0]));
+        assert_eq!(int.len(), 500);
+        for (i, address) in addresses[0].iter().enumerate() {
+            assert_eq!(int.intern(i.to_string()).as_ref() as *const String as usize, *address);
+        }
+    }
+}
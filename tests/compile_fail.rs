@@ -0,0 +1,6 @@
+//! The references produced by an interner must never outlive it.
+
+#[test]
+fn compile_fail() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
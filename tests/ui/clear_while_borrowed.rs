@@ -0,0 +1,8 @@
+use libintern::Interner;
+
+fn main() {
+    let mut interner = Interner::new();
+    let a = interner.intern(String::from("a"));
+    interner.clear();
+    println!("{}", a);
+}
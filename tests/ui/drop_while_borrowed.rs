@@ -0,0 +1,8 @@
+use libintern::Interner;
+
+fn main() {
+    let interner = Interner::new();
+    let a = interner.intern(String::from("a"));
+    drop(interner);
+    println!("{}", a);
+}
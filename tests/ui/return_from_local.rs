@@ -0,0 +1,10 @@
+use libintern::{Intern, Interner};
+
+fn intern_locally() -> Intern<'static, String> {
+    let interner = Interner::new();
+    interner.intern(String::from("a"))
+}
+
+fn main() {
+    println!("{}", intern_locally());
+}